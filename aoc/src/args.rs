@@ -0,0 +1,149 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub const USAGE: &str = "Usage: aoc [--day <day>] [--part <part>] [--input <path>]
+           [--bench [--runs <count>] [--history <path>]]";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Options {
+    pub day: Option<u32>,
+    pub part: Option<u32>,
+    pub input: Option<String>,
+    pub bench: bool,
+    pub runs: Option<u32>,
+    pub history: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArgsError {
+    MissingValue(String),
+    InvalidNumber(String, String),
+    UnknownArgument(String),
+    InputWithoutDay,
+    NoRuns,
+    BenchOnly(String),
+}
+
+impl Display for ArgsError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ArgsError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            ArgsError::InvalidNumber(flag, value) => {
+                write!(f, "{} must be a number, got {:?}", flag, value)
+            }
+            ArgsError::UnknownArgument(arg) => write!(f, "Unknown argument {:?}", arg),
+            ArgsError::InputWithoutDay => write!(f, "--input can only be used with --day"),
+            ArgsError::NoRuns => write!(f, "--runs must be at least 1"),
+            ArgsError::BenchOnly(flag) => write!(f, "{} can only be used with --bench", flag),
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+fn number(flag: &str, value: Option<String>) -> Result<u32, ArgsError> {
+    let value = value.ok_or_else(|| ArgsError::MissingValue(flag.to_string()))?;
+    value
+        .parse()
+        .map_err(|_| ArgsError::InvalidNumber(flag.to_string(), value))
+}
+
+pub fn parse<I: Iterator<Item = String>>(mut args: I) -> Result<Options, ArgsError> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => options.day = Some(number(&arg, args.next())?),
+            "--part" => options.part = Some(number(&arg, args.next())?),
+            "--input" => {
+                options.input = Some(args.next().ok_or(ArgsError::MissingValue(arg))?);
+            }
+            "--bench" => options.bench = true,
+            "--runs" => options.runs = Some(number(&arg, args.next())?),
+            "--history" => {
+                options.history = Some(args.next().ok_or(ArgsError::MissingValue(arg))?);
+            }
+            _ => return Err(ArgsError::UnknownArgument(arg)),
+        }
+    }
+    // Every day has its own input, so one file for all of them makes no sense.
+    if options.input.is_some() && options.day.is_none() {
+        return Err(ArgsError::InputWithoutDay);
+    }
+    if options.runs == Some(0) {
+        return Err(ArgsError::NoRuns);
+    }
+    if !options.bench {
+        if options.runs.is_some() {
+            return Err(ArgsError::BenchOnly("--runs".to_string()));
+        }
+        if options.history.is_some() {
+            return Err(ArgsError::BenchOnly("--history".to_string()));
+        }
+    }
+    Ok(options)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Options, ArgsError> {
+        parse(args.split_whitespace().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse_str(""), Ok(Options::default()));
+        assert_eq!(
+            parse_str("--day 7 --part 2 --input other.txt"),
+            Ok(Options {
+                day: Some(7),
+                part: Some(2),
+                input: Some("other.txt".to_string()),
+                ..Options::default()
+            })
+        );
+        assert_eq!(
+            parse_str("--bench --day 16 --runs 3 --history bench.json"),
+            Ok(Options {
+                day: Some(16),
+                bench: true,
+                runs: Some(3),
+                history: Some("bench.json".to_string()),
+                ..Options::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_str("--day"),
+            Err(ArgsError::MissingValue("--day".to_string()))
+        );
+        assert_eq!(
+            parse_str("--part two"),
+            Err(ArgsError::InvalidNumber(
+                "--part".to_string(),
+                "two".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_str("--days 1"),
+            Err(ArgsError::UnknownArgument("--days".to_string()))
+        );
+        assert_eq!(
+            parse_str("--input input.txt"),
+            Err(ArgsError::InputWithoutDay)
+        );
+        assert_eq!(parse_str("--bench --runs 0"), Err(ArgsError::NoRuns));
+        assert_eq!(
+            parse_str("--runs 3"),
+            Err(ArgsError::BenchOnly("--runs".to_string()))
+        );
+        assert_eq!(
+            parse_str("--history bench.json"),
+            Err(ArgsError::BenchOnly("--history".to_string()))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::fs::{read_to_string, write};
+use std::io::ErrorKind;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u32,
+    pub part: u32,
+    pub median_ns: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    // Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub samples: u32,
+    pub timings: Vec<Timing>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl Run {
+    pub fn new(samples: u32) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+        Run {
+            timestamp,
+            samples,
+            timings: vec![],
+        }
+    }
+
+    pub fn timing(&self, day: u32, part: u32) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|timing| (timing.day, timing.part) == (day, part))
+            .map(|timing| Duration::from_nanos(timing.median_ns))
+    }
+}
+
+impl History {
+    // The most recent run that timed the given solution, so that benchmarking
+    // a single day still compares against the last time that day was run.
+    pub fn previous(&self, day: u32, part: u32) -> Option<Duration> {
+        self.runs.iter().rev().find_map(|run| run.timing(day, part))
+    }
+}
+
+pub fn load_history(filename: &str) -> Result<History, Box<dyn Error>> {
+    match read_to_string(filename) {
+        Ok(contents) => {
+            Ok(serde_json::from_str(&contents).map_err(|err| format!("{}: {}", filename, err))?)
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(History::default()),
+        Err(err) => Err(format!("{}: {}", filename, err).into()),
+    }
+}
+
+pub fn save_history(filename: &str, history: &History) -> Result<(), Box<dyn Error>> {
+    write(filename, serde_json::to_string_pretty(history)?)
+        .map_err(|err| format!("{}: {}", filename, err))?;
+    Ok(())
+}
+
+pub fn median(samples: &mut [Duration]) -> Duration {
+    if samples.is_empty() {
+        return Duration::default();
+    }
+    samples.sort();
+    let middle = samples.len() / 2;
+    if samples.len().is_multiple_of(2) {
+        (samples[middle - 1] + samples[middle]) / 2
+    } else {
+        samples[middle]
+    }
+}
+
+fn change(previous: Option<Duration>, current: Duration) -> String {
+    match previous {
+        Some(previous) if previous.as_nanos() > 0 => {
+            let ratio = current.as_secs_f64() / previous.as_secs_f64();
+            format!("{:+.1}%", (ratio - 1.0) * 100.0)
+        }
+        _ => "new".to_string(),
+    }
+}
+
+pub fn table(history: &History, current: &Run) -> String {
+    let mut rows = vec![format!(
+        "{:>3} {:>4} {:>14} {:>14} {:>8}",
+        "Day", "Part", "Previous", "Current", "Change"
+    )];
+    for timing in &current.timings {
+        let previous = history.previous(timing.day, timing.part);
+        let current = Duration::from_nanos(timing.median_ns);
+        rows.push(format!(
+            "{:>3} {:>4} {:>14} {:>14} {:>8}",
+            timing.day,
+            timing.part,
+            previous.map_or("-".to_string(), |previous| format!("{:.2?}", previous)),
+            format!("{:.2?}", current),
+            change(previous, current)
+        ));
+    }
+    rows.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(timestamp: u64, timings: &[(u32, u32, u64)]) -> Run {
+        Run {
+            timestamp,
+            samples: 5,
+            timings: timings
+                .iter()
+                .map(|&(day, part, median_ns)| Timing {
+                    day,
+                    part,
+                    median_ns,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_median() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&mut []), Duration::default());
+        assert_eq!(median(&mut [ms(5), ms(1), ms(3)]), ms(3));
+        assert_eq!(median(&mut [ms(4), ms(1), ms(100), ms(2)]), ms(3));
+    }
+
+    #[test]
+    fn test_previous() {
+        let history = History {
+            runs: vec![run(1, &[(1, 1, 100), (1, 2, 200)]), run(2, &[(1, 1, 150)])],
+        };
+        assert_eq!(history.previous(1, 1), Some(Duration::from_nanos(150)));
+        assert_eq!(history.previous(1, 2), Some(Duration::from_nanos(200)));
+        assert_eq!(history.previous(2, 1), None);
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let history = History {
+            runs: vec![run(1, &[(16, 2, 123_456)])],
+        };
+        let json = serde_json::to_string(&history).unwrap();
+        assert_eq!(serde_json::from_str::<History>(&json).unwrap(), history);
+    }
+
+    #[test]
+    fn test_table() {
+        let history = History {
+            runs: vec![run(1, &[(10, 1, 2_000_000)])],
+        };
+        let table = table(&history, &run(2, &[(10, 1, 1_000_000), (10, 2, 5_000)]));
+        let rows: Vec<&str> = table.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].contains("2.00ms"));
+        assert!(rows[1].contains("1.00ms"));
+        assert!(rows[1].ends_with("-50.0%"));
+        assert!(rows[2].contains(" - "));
+        assert!(rows[2].ends_with("new"));
+    }
+}
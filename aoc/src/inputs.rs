@@ -0,0 +1,234 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const ENDPOINT_VAR: &str = "AOC_ENDPOINT";
+const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+#[cfg(feature = "fetch")]
+const USER_AGENT: &str = "github.com/meiamsome/advent-of-code-2019";
+
+#[derive(Debug, PartialEq)]
+pub enum InputError {
+    NotCached { day: u32, path: PathBuf },
+    Fetch { day: u32, reason: String },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            InputError::NotCached { day, path } => write!(
+                f,
+                "No input for day {} at {} (set {} to fetch it)",
+                day,
+                path.display(),
+                SESSION_VAR
+            ),
+            InputError::Fetch { day, reason } => {
+                write!(f, "Failed to fetch input for day {}: {}", day, reason)
+            }
+        }
+    }
+}
+
+impl Error for InputError {}
+
+// Downloads inputs with the session cookie of a logged in user.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fetcher {
+    pub endpoint: String,
+    pub session: String,
+}
+
+impl Fetcher {
+    pub fn new(endpoint: &str, session: &str) -> Self {
+        Fetcher {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    // Only available when a session token has been given.
+    pub fn from_env() -> Option<Self> {
+        let session = std::env::var(SESSION_VAR).ok()?;
+        let endpoint = std::env::var(ENDPOINT_VAR).unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string());
+        Some(Fetcher::new(&endpoint, &session))
+    }
+
+    #[cfg(feature = "fetch")]
+    pub fn url(&self, day: u32) -> String {
+        format!("{}/2019/day/{}/input", self.endpoint, day)
+    }
+
+    #[cfg(feature = "fetch")]
+    pub fn fetch(&self, day: u32) -> Result<String, InputError> {
+        let fetch_error = |reason: String| InputError::Fetch { day, reason };
+        ureq::get(&self.url(day))
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, _) => fetch_error(format!("HTTP status {}", status)),
+                err => fetch_error(err.to_string()),
+            })?
+            .into_string()
+            .map_err(|err| fetch_error(err.to_string()))
+    }
+
+    #[cfg(not(feature = "fetch"))]
+    pub fn fetch(&self, day: u32) -> Result<String, InputError> {
+        Err(InputError::Fetch {
+            day,
+            reason: "aoc was built without the fetch feature".to_string(),
+        })
+    }
+}
+
+// Inputs are the same for both parts of a day, so they're stored once per day.
+// Once an input is in the cache it is never fetched again.
+pub struct InputCache {
+    dir: PathBuf,
+    fetcher: Option<Fetcher>,
+}
+
+impl InputCache {
+    pub fn new<P: AsRef<Path>>(dir: P, fetcher: Option<Fetcher>) -> Self {
+        InputCache {
+            dir: dir.as_ref().to_path_buf(),
+            fetcher,
+        }
+    }
+
+    pub fn path(&self, day: u32) -> PathBuf {
+        self.dir.join(format!("day-{:02}.txt", day))
+    }
+
+    pub fn get(&self, day: u32) -> Result<String, Box<dyn Error>> {
+        let path = self.path(day);
+        if path.exists() {
+            return Ok(read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?);
+        }
+        let fetcher = self.fetcher.as_ref().ok_or_else(|| InputError::NotCached {
+            day,
+            path: path.clone(),
+        })?;
+        let input = fetcher.fetch(day)?;
+        create_dir_all(&self.dir).map_err(|err| format!("{}: {}", self.dir.display(), err))?;
+        write(&path, &input).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::remove_dir_all;
+    #[cfg(feature = "fetch")]
+    use std::io::{BufRead, BufReader, Write};
+    #[cfg(feature = "fetch")]
+    use std::net::TcpListener;
+    #[cfg(feature = "fetch")]
+    use std::thread;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}-{}", name, std::process::id()));
+        let _ = remove_dir_all(&dir);
+        dir
+    }
+
+    // Answers a single request with the given status and body, handing back
+    // the request line and headers it was sent.
+    #[cfg(feature = "fetch")]
+    fn serve_once(status: &str, body: &str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let request: Vec<String> = BufReader::new(stream.try_clone().unwrap())
+                .lines()
+                .map(|line| line.unwrap())
+                .take_while(|line| !line.is_empty())
+                .collect();
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (endpoint, handle)
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = temp_dir("cached");
+        create_dir_all(&dir).unwrap();
+        write(dir.join("day-03.txt"), "R8,U5\n").unwrap();
+        let cache = InputCache::new(&dir, None);
+        assert_eq!(cache.get(3).unwrap(), "R8,U5\n");
+        assert_eq!(
+            cache.get(4).unwrap_err().to_string(),
+            InputError::NotCached {
+                day: 4,
+                path: dir.join("day-04.txt")
+            }
+            .to_string()
+        );
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "fetch")]
+    fn test_fetch_once() {
+        let dir = temp_dir("fetch");
+        let (endpoint, server) = serve_once("200 OK", "1969\n100756\n");
+        let cache = InputCache::new(&dir, Some(Fetcher::new(&endpoint, "abc123")));
+        assert_eq!(cache.get(1).unwrap(), "1969\n100756\n");
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2019/day/1/input HTTP/1.1");
+        assert!(request
+            .iter()
+            .any(|header| header.eq_ignore_ascii_case("cookie: session=abc123")));
+        // The server only answers once, so this has to come from the cache.
+        assert_eq!(cache.get(1).unwrap(), "1969\n100756\n");
+        assert_eq!(
+            read_to_string(dir.join("day-01.txt")).unwrap(),
+            "1969\n100756\n"
+        );
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "fetch")]
+    fn test_fetch_error() {
+        let dir = temp_dir("error");
+        let (endpoint, server) = serve_once("404 Not Found", "Not yet!");
+        let fetcher = Fetcher::new(&format!("{}/", endpoint), "abc123");
+        let cache = InputCache::new(&dir, Some(fetcher));
+        assert_eq!(
+            cache.get(25).unwrap_err().to_string(),
+            InputError::Fetch {
+                day: 25,
+                reason: "HTTP status 404".to_string()
+            }
+            .to_string()
+        );
+        server.join().unwrap();
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    #[cfg(not(feature = "fetch"))]
+    fn test_fetch_disabled() {
+        let dir = temp_dir("disabled");
+        let cache = InputCache::new(&dir, Some(Fetcher::new("http://localhost", "abc123")));
+        assert_eq!(
+            cache.get(2).unwrap_err().to_string(),
+            "Failed to fetch input for day 2: aoc was built without the fetch feature"
+        );
+        assert!(!dir.exists());
+    }
+}
@@ -0,0 +1,256 @@
+use std::error::Error;
+use std::fs::read_to_string;
+use std::time::{Duration, Instant};
+
+use solution::answers::{expected, load_answers, ExpectedAnswer};
+use solution::{Answer, Solution, INPUTS};
+
+use crate::args::Options;
+use crate::bench::{load_history, median, save_history, table, Run, Timing};
+use crate::inputs::{Fetcher, InputCache};
+
+mod args;
+mod bench;
+mod inputs;
+
+const SOLUTIONS: &[&dyn Solution] = &[
+    &day_1_part_1::Solver,
+    &day_1_part_2::Solver,
+    &day_2_part_1::Solver,
+    &day_2_part_2::Solver,
+    &day_3_part_1::Solver,
+    &day_3_part_2::Solver,
+    &day_4_part_1::Solver,
+    &day_4_part_2::Solver,
+    &day_5_part_1::Solver,
+    &day_6_part_1::Solver,
+    &day_6_part_2::Solver,
+    &day_7_part_1::Solver,
+    &day_7_part_2::Solver,
+    &day_8_part_1::Solver,
+    &day_8_part_2::Solver,
+    &day_9_part_1::Solver,
+    &day_10_part_1::Solver,
+    &day_10_part_2::Solver,
+    &day_11_part_1::Solver,
+    &day_11_part_2::Solver,
+    &day_12_part_1::Solver,
+    &day_12_part_2::Solver,
+    &day_13_part_1::Solver,
+    &day_13_part_2::Solver,
+    &day_14_part_1::Solver,
+    &day_14_part_2::Solver,
+    &day15part1::Solver,
+    &day_15_part_2::Solver,
+    &day16part1::Solver,
+    &day_16_part_2::Solver,
+    &day_17_part_1::Solver,
+];
+
+const ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+const HISTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../bench-history.json");
+const DEFAULT_RUNS: u32 = 5;
+
+// An explicit --input wins, otherwise the day's input comes from the shared
+// cache, which may fetch it.
+fn load_input(
+    options: &Options,
+    cache: &InputCache,
+    solution: &dyn Solution,
+) -> Result<String, Box<dyn Error>> {
+    match &options.input {
+        Some(path) => Ok(read_to_string(path).map_err(|err| format!("{}: {}", path, err))?),
+        None => cache.get(solution.day()),
+    }
+}
+
+fn run(solution: &dyn Solution, input: &str) -> Result<(Answer, Duration), Box<dyn Error>> {
+    let start = Instant::now();
+    let answer = solution.solve(input)?;
+    Ok((answer, start.elapsed()))
+}
+
+// Counts a wrong answer as a failure, but only when we know the right one.
+fn check(
+    answers: &[ExpectedAnswer],
+    solution: &dyn Solution,
+    input: &str,
+    answer: &Answer,
+) -> bool {
+    match expected(answers, solution.day(), solution.part(), input) {
+        Some(expected) if expected != answer.to_string() => {
+            println!(" wrong, expected {}", expected);
+            false
+        }
+        _ => {
+            println!();
+            true
+        }
+    }
+}
+
+fn run_once(
+    options: &Options,
+    cache: &InputCache,
+    selected: &[&dyn Solution],
+    answers: &[ExpectedAnswer],
+) -> usize {
+    let mut total = Duration::default();
+    let mut failures = 0;
+    for &solution in selected {
+        let (day, part) = (solution.day(), solution.part());
+        let result = load_input(options, cache, solution).and_then(|input| {
+            run(solution, &input).map(|(answer, elapsed)| (input, answer, elapsed))
+        });
+        match result {
+            Ok((input, answer, elapsed)) => {
+                total += elapsed;
+                print!("Day {:2} part {}: {} ({:?})", day, part, answer, elapsed);
+                if !check(answers, solution, &input, &answer) {
+                    failures += 1;
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                println!("Day {:2} part {}: failed: {}", day, part, err);
+            }
+        }
+    }
+    println!("Total: {:?}", total);
+    failures
+}
+
+// Times each solution several times and keeps the median, so one slow run
+// doesn't look like a regression.
+fn bench(
+    options: &Options,
+    cache: &InputCache,
+    selected: &[&dyn Solution],
+    answers: &[ExpectedAnswer],
+) -> Result<usize, Box<dyn Error>> {
+    let runs = options.runs.unwrap_or(DEFAULT_RUNS);
+    let filename = options.history.as_deref().unwrap_or(HISTORY);
+    let mut history = load_history(filename)?;
+    let mut current = Run::new(runs);
+    let mut failures = 0;
+    for &solution in selected {
+        let (day, part) = (solution.day(), solution.part());
+        let input = match load_input(options, cache, solution) {
+            Ok(input) => input,
+            Err(err) => {
+                failures += 1;
+                println!("Day {:2} part {}: failed: {}", day, part, err);
+                continue;
+            }
+        };
+        let mut samples = vec![];
+        for _ in 0..runs {
+            match run(solution, &input) {
+                Ok((answer, elapsed)) => {
+                    if samples.is_empty() {
+                        print!("Day {:2} part {}: {}", day, part, answer);
+                        if !check(answers, solution, &input, &answer) {
+                            failures += 1;
+                            break;
+                        }
+                    }
+                    samples.push(elapsed);
+                }
+                Err(err) => {
+                    failures += 1;
+                    println!("Day {:2} part {}: failed: {}", day, part, err);
+                    break;
+                }
+            }
+        }
+        if samples.len() == runs as usize {
+            current.timings.push(Timing {
+                day,
+                part,
+                median_ns: median(&mut samples).as_nanos() as u64,
+            });
+        }
+    }
+    println!();
+    println!("{}", table(&history, &current));
+    if !current.timings.is_empty() {
+        history.runs.push(current);
+        save_history(filename, &history)?;
+    }
+    Ok(failures)
+}
+
+fn run_all() -> Result<(), Box<dyn Error>> {
+    let options =
+        args::parse(std::env::args().skip(1)).map_err(|err| format!("{}\n{}", err, args::USAGE))?;
+    let selected: Vec<&dyn Solution> = SOLUTIONS
+        .iter()
+        .copied()
+        .filter(|solution| {
+            options.day.is_none_or(|day| day == solution.day())
+                && options.part.is_none_or(|part| part == solution.part())
+        })
+        .collect();
+    if selected.is_empty() {
+        return Err("No solutions match the given day and part".into());
+    }
+    let answers = load_answers(ANSWERS)?;
+    let cache = InputCache::new(INPUTS, Fetcher::from_env());
+
+    let failures = if options.bench {
+        bench(&options, &cache, &selected, &answers)?
+    } else {
+        run_once(&options, &cache, &selected, &answers)
+    };
+    if failures > 0 {
+        return Err(format!("{} solutions failed", failures).into());
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(err) = run_all() {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_solutions_are_unique() {
+        let keys: HashSet<_> = SOLUTIONS
+            .iter()
+            .map(|solution| (solution.day(), solution.part()))
+            .collect();
+        assert_eq!(keys.len(), SOLUTIONS.len());
+    }
+
+    // Runs every solution against its input, so a change to a shared crate
+    // that breaks an old day is caught here.
+    #[test]
+    fn test_answers() {
+        let answers = load_answers(ANSWERS).unwrap();
+        let cache = InputCache::new(INPUTS, None);
+        for &solution in SOLUTIONS {
+            let (day, part) = (solution.day(), solution.part());
+            let input = load_input(&Options::default(), &cache, solution).unwrap();
+            let expected = expected(&answers, day, part, &input).unwrap_or_else(|| {
+                panic!(
+                    "No answer recorded for day {} part {} with this input",
+                    day, part
+                )
+            });
+            assert_eq!(
+                solution.solve(&input).unwrap().to_string(),
+                expected,
+                "Day {} part {}",
+                day,
+                part
+            );
+        }
+    }
+}
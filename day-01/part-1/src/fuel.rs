@@ -0,0 +1,300 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Sub};
+use std::str::FromStr;
+
+// Anything that can be weighed: u64 for the puzzle, or a bignum when the
+// masses get silly.
+pub trait Mass:
+    Clone
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Div<Output = Self>
+    + From<u32>
+    + FromStr
+    + Display
+{
+}
+
+impl<T> Mass for T where
+    T: Clone
+        + PartialOrd
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + From<u32>
+        + FromStr
+        + Display
+{
+}
+
+// mass / divisor - subtrahend, never going below zero. The fields are private
+// so the divisor can only be set through `Formula::linear`, which rejects zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Linear<T> {
+    divisor: T,
+    subtrahend: T,
+}
+
+pub enum Formula<T> {
+    Linear(Linear<T>),
+    Custom(Box<dyn Fn(&T) -> T>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ZeroDivisor;
+
+impl Display for ZeroDivisor {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "The fuel formula's divisor can't be zero")
+    }
+}
+
+impl Error for ZeroDivisor {}
+
+impl<T: Mass> Formula<T> {
+    // The formula from the puzzle.
+    pub fn standard() -> Self {
+        Formula::Linear(Linear {
+            divisor: T::from(3),
+            subtrahend: T::from(2),
+        })
+    }
+
+    pub fn linear(divisor: T, subtrahend: T) -> Result<Self, ZeroDivisor> {
+        if divisor == T::from(0) {
+            return Err(ZeroDivisor);
+        }
+        Ok(Formula::Linear(Linear {
+            divisor,
+            subtrahend,
+        }))
+    }
+
+    pub fn fuel(&self, mass: &T) -> T {
+        match self {
+            Formula::Linear(Linear {
+                divisor,
+                subtrahend,
+            }) => {
+                let quotient = mass.clone() / divisor.clone();
+                if quotient > *subtrahend {
+                    quotient - subtrahend.clone()
+                } else {
+                    T::from(0)
+                }
+            }
+            Formula::Custom(formula) => formula(mass),
+        }
+    }
+
+    pub fn fuel_for_fuel(&self, mass: T) -> FuelCalculation<'_, T> {
+        FuelCalculation {
+            formula: self,
+            current: mass,
+        }
+    }
+
+    pub fn report(&self, module: &Module<T>) -> ModuleReport<T> {
+        let mut fuel = self.fuel_for_fuel(module.mass.clone());
+        let initial = fuel.next().unwrap_or_else(|| T::from(0));
+        ModuleReport {
+            line: module.line,
+            mass: module.mass.clone(),
+            initial,
+            fuel_for_fuel: sum(fuel),
+        }
+    }
+}
+
+// Each step is the fuel needed to carry the previous one. Stops when no more
+// fuel is needed, or when a custom formula stops shrinking the mass as it
+// would otherwise never finish.
+pub struct FuelCalculation<'a, T> {
+    formula: &'a Formula<T>,
+    current: T,
+}
+
+impl<'a, T: Mass> Iterator for FuelCalculation<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let fuel = self.formula.fuel(&self.current);
+        if fuel <= T::from(0) || fuel >= self.current {
+            return None;
+        }
+        self.current = fuel.clone();
+        Some(fuel)
+    }
+}
+
+pub fn sum<T: Mass, I: IntoIterator<Item = T>>(values: I) -> T {
+    values
+        .into_iter()
+        .fold(T::from(0), |total, value| total + value)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Module<T> {
+    pub line: usize,
+    pub mass: T,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleReport<T> {
+    pub line: usize,
+    pub mass: T,
+    pub initial: T,
+    pub fuel_for_fuel: T,
+}
+
+impl<T: Mass> ModuleReport<T> {
+    pub fn total(&self) -> T {
+        self.initial.clone() + self.fuel_for_fuel.clone()
+    }
+}
+
+impl<T: Mass> Display for ModuleReport<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "Line {}: mass {} needs {} fuel + {} for the fuel = {}",
+            self.line,
+            self.mass,
+            self.initial,
+            self.fuel_for_fuel,
+            self.total()
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub value: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "Line {}: invalid mass {:?}", self.line, self.value)
+    }
+}
+
+impl Error for ParseError {}
+
+// One mass per line, blank lines are skipped.
+pub fn parse_modules<T: Mass>(input: &str) -> Result<Vec<Module<T>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index + 1, text.trim()))
+        .filter(|(_, text)| !text.is_empty())
+        .map(|(line, text)| {
+            text.parse()
+                .map(|mass| Module { line, mass })
+                .map_err(|_| ParseError {
+                    line,
+                    value: text.to_string(),
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use num_bigint::BigUint;
+
+    #[test]
+    fn test_standard_fuel() {
+        let formula = Formula::<u64>::standard();
+        assert_eq!(formula.fuel(&12), 2);
+        assert_eq!(formula.fuel(&14), 2);
+        assert_eq!(formula.fuel(&1969), 654);
+        assert_eq!(formula.fuel(&100_756), 33583);
+        assert_eq!(formula.fuel(&5), 0);
+    }
+
+    #[test]
+    fn test_fuel_for_fuel() {
+        let formula = Formula::<u64>::standard();
+        assert_eq!(sum(formula.fuel_for_fuel(14)), 2);
+        assert_eq!(sum(formula.fuel_for_fuel(1969)), 966);
+        assert_eq!(sum(formula.fuel_for_fuel(100_756)), 50346);
+    }
+
+    #[test]
+    fn test_custom_formula() {
+        let halve = Formula::<u64>::Custom(Box::new(|mass| mass / 2));
+        assert_eq!(
+            halve.fuel_for_fuel(100).collect::<Vec<_>>(),
+            vec![50, 25, 12, 6, 3, 1]
+        );
+        // Would never finish if we kept going.
+        let same = Formula::<u64>::Custom(Box::new(|mass| *mass));
+        assert_eq!(same.fuel_for_fuel(100).count(), 0);
+        let linear = Formula::linear(10u64, 1).unwrap();
+        assert_eq!(linear.fuel(&1000), 99);
+        assert!(Formula::linear(0u64, 1).is_err());
+        assert_eq!(
+            Formula::linear(BigUint::from(0u32), BigUint::from(2u32)).err(),
+            Some(ZeroDivisor)
+        );
+    }
+
+    #[test]
+    fn test_bignum() {
+        let formula = Formula::<BigUint>::standard();
+        let mass: BigUint = "1000000000000000000000000000000".parse().unwrap();
+        assert_eq!(
+            formula.fuel(&mass).to_string(),
+            "333333333333333333333333333331"
+        );
+        assert_eq!(
+            sum(formula.fuel_for_fuel(BigUint::from(100_756u32))),
+            BigUint::from(50346u32)
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let formula = Formula::<u64>::standard();
+        let report = formula.report(&Module {
+            line: 3,
+            mass: 1969,
+        });
+        assert_eq!(
+            report,
+            ModuleReport {
+                line: 3,
+                mass: 1969,
+                initial: 654,
+                fuel_for_fuel: 312,
+            }
+        );
+        assert_eq!(report.total(), 966);
+        assert_eq!(
+            report.to_string(),
+            "Line 3: mass 1969 needs 654 fuel + 312 for the fuel = 966"
+        );
+    }
+
+    #[test]
+    fn test_parse_modules() {
+        assert_eq!(
+            parse_modules::<u64>("12\n\n14\n"),
+            Ok(vec![
+                Module { line: 1, mass: 12 },
+                Module { line: 3, mass: 14 }
+            ])
+        );
+        assert_eq!(
+            parse_modules::<u64>("12\n-14\n"),
+            Err(ParseError {
+                line: 2,
+                value: "-14".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
+pub mod fuel;
+
+use self::fuel::{parse_modules, sum, Formula};
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let formula = Formula::<u64>::standard();
+    let modules = parse_modules(input)?;
+    Ok(Answer::from(sum(modules
+        .iter()
+        .map(|module| formula.fuel(&module.mass)))))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn module_list_to_fuel_testcase() {
+        assert_eq!(solve("12\n14\n").unwrap(), Answer::Number(4))
+    }
+}
@@ -0,0 +1,38 @@
+use std::error::Error;
+
+use day_1_part_1::fuel::{parse_modules, sum, Formula};
+use solution::{Answer, Solution};
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let formula = Formula::<u64>::standard();
+    let modules = parse_modules(input)?;
+    Ok(Answer::from(sum(modules
+        .iter()
+        .map(|module| formula.report(module).total()))))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn full_fuel_amount_for_payloads() {
+        assert_eq!(solve("14\n1969\n100756").unwrap(), Answer::Number(51314))
+    }
+}
@@ -0,0 +1,26 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+use vm::lang::load_from_str;
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut vm = load_from_str(input)?;
+    for _ in vm.by_ref() {}
+    Ok(Answer::from(vm.memory.memory[0]))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
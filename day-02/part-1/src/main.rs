@@ -1,8 +1,14 @@
-use vm::lang::load_from_file;
+use std::fs::File;
+use std::io::prelude::*;
+
+use solution::input_path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut vm = load_from_file("./input.txt")?;
-    while let Some(_) = vm.next() {}
-    println!("{:?}", vm.memory.memory);
+    let mut contents = String::new();
+    {
+        let mut file = File::open(input_path(2))?;
+        file.read_to_string(&mut contents)?;
+    }
+    println!("{}", day_2_part_1::solve(&contents)?);
     Ok(())
 }
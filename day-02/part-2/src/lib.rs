@@ -0,0 +1,35 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+use vm::program::Program;
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let program = Program::parse(input)?;
+    let mut vm = program.instantiate();
+    for x in 0..=99 {
+        for y in 0..=99 {
+            program.variant().noun(x).verb(y).reset(&mut vm);
+            for _ in vm.by_ref() {}
+            if vm.memory.memory[0] == 19_690_720 {
+                return Ok(Answer::from(100 * x + y));
+            }
+        }
+    }
+    Err("No solution found!".into())
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
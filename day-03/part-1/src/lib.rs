@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
+pub mod parser;
+pub mod wire;
+
+use self::parser::parse_wires;
+use self::wire::{closest, crossings, Metric, Point, Wire};
+
+pub fn find_intersections(instruction_sets: &str) -> Result<HashSet<Point>, Box<dyn Error>> {
+    Ok(crossings(&parse_wires(instruction_sets)?)
+        .into_iter()
+        .map(|crossing| crossing.point)
+        .collect())
+}
+
+pub fn smallest_intersection(
+    instruction_sets: &str,
+) -> Result<Option<(Point, i64)>, Box<dyn Error>> {
+    Ok(
+        closest(&parse_wires(instruction_sets)?, 2, Metric::Manhattan)
+            .map(|(crossing, distance)| (crossing.point, distance)),
+    )
+}
+
+// Works with any number of wires, as long as two of them cross somewhere.
+pub fn solve_wires(wires: &[Wire]) -> Result<Answer, Box<dyn Error>> {
+    let (_, value) = closest(wires, 2, Metric::Manhattan).ok_or("The wires never cross")?;
+    Ok(Answer::from(value))
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    solve_wires(&parse_wires(input)?)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::find_intersections;
+    use super::smallest_intersection;
+
+    #[test]
+    fn find_intersections_1() {
+        assert_eq!(
+            find_intersections("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap(),
+            vec!((3, 3), (6, 5),).into_iter().collect()
+        )
+    }
+
+    #[test]
+    fn find_intersections_2() {
+        assert_eq!(
+            find_intersections(
+                "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83"
+            )
+            .unwrap(),
+            vec!((146, 46), (155, 11), (158, -12), (155, 4),)
+                .into_iter()
+                .collect()
+        )
+    }
+
+    #[test]
+    fn find_intersections_3() {
+        assert_eq!(
+            find_intersections(
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
+            )
+            .unwrap(),
+            vec!((107, 51), (107, 47), (107, 71), (124, 11), (157, 18),)
+                .into_iter()
+                .collect()
+        )
+    }
+
+    #[test]
+    fn smallest_intersection_1() {
+        assert_eq!(
+            smallest_intersection("R8,U5,L5,D3\nU7,R6,D4,L4").unwrap(),
+            Some(((3, 3), 6))
+        )
+    }
+
+    #[test]
+    fn smallest_intersection_2() {
+        assert_eq!(
+            smallest_intersection(
+                "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83"
+            )
+            .unwrap(),
+            Some(((155, 4), 159))
+        )
+    }
+
+    #[test]
+    fn smallest_intersection_3() {
+        assert_eq!(
+            smallest_intersection(
+                "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7"
+            )
+            .unwrap(),
+            Some(((124, 11), 135))
+        )
+    }
+}
@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+use std::path::Path;
+
+use crate::wire::{Point, Wire};
+
+// Instructions are indexed from zero within their wire.
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    UnknownDirection { index: usize, instruction: String },
+    InvalidLength { index: usize, instruction: String },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ParseError::UnknownDirection { index, instruction } => write!(
+                f,
+                "Instruction {} ({:?}) has an unknown direction",
+                index, instruction
+            ),
+            ParseError::InvalidLength { index, instruction } => write!(
+                f,
+                "Instruction {} ({:?}) has an invalid length",
+                index, instruction
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// Which of the wires being loaded was at fault, counting from zero.
+#[derive(Debug, PartialEq)]
+pub struct LoadError {
+    pub wire: usize,
+    pub error: ParseError,
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "Wire {}: {}", self.wire, self.error)
+    }
+}
+
+impl Error for LoadError {}
+
+fn direction(name: &str) -> Option<Point> {
+    Some(match name {
+        "U" => (0, 1),
+        "D" => (0, -1),
+        "L" => (-1, 0),
+        "R" => (1, 0),
+        "NE" => (1, 1),
+        "NW" => (-1, 1),
+        "SE" => (1, -1),
+        "SW" => (-1, -1),
+        _ => return None,
+    })
+}
+
+// An instruction is a direction, either one of UDLR or a diagonal like NE,
+// followed by a length which may be zero.
+fn parse_instruction(index: usize, instruction: &str) -> Result<(Point, i64), ParseError> {
+    let split = instruction
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(instruction.len());
+    let (name, len) = instruction.split_at(split);
+    let direction = direction(name).ok_or_else(|| ParseError::UnknownDirection {
+        index,
+        instruction: instruction.to_string(),
+    })?;
+    let len = len.parse::<u32>().map_err(|_| ParseError::InvalidLength {
+        index,
+        instruction: instruction.to_string(),
+    })?;
+    Ok((direction, i64::from(len)))
+}
+
+pub fn parse_wire(instructions: &str) -> Result<Wire, ParseError> {
+    let moves = instructions
+        .trim()
+        .split(',')
+        .enumerate()
+        .map(|(index, instruction)| parse_instruction(index, instruction.trim()))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    Ok(Wire::from_moves(moves))
+}
+
+// One wire per line, blank lines are skipped.
+pub fn parse_wires(input: &str) -> Result<Vec<Wire>, LoadError> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(wire, line)| parse_wire(line).map_err(|error| LoadError { wire, error }))
+        .collect()
+}
+
+// Wires never contain slashes or dots, so anything that does is meant to be a
+// file even if it isn't there.
+fn is_path(source: &str) -> bool {
+    source.contains(['/', '\\', '.']) || Path::new(source).exists()
+}
+
+// Each source is either a file holding one wire per line, or a wire given
+// directly, say as a command line argument.
+pub fn load<S: AsRef<str>>(sources: &[S]) -> Result<Vec<Wire>, Box<dyn Error>> {
+    let mut wires = vec![];
+    for source in sources {
+        let source = source.as_ref();
+        let text = if is_path(source) {
+            read_to_string(source).map_err(|err| format!("{}: {}", source, err))?
+        } else {
+            source.to_string()
+        };
+        let offset = wires.len();
+        wires.extend(parse_wires(&text).map_err(|err| LoadError {
+            wire: err.wire + offset,
+            error: err.error,
+        })?);
+    }
+    Ok(wires)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::wire::Segment;
+
+    #[test]
+    fn test_parse_wire() {
+        assert_eq!(
+            parse_wire("R8, NE2,U0,SW3").unwrap().segments,
+            vec![
+                Segment {
+                    start: (0, 0),
+                    end: (8, 0),
+                    steps: 0
+                },
+                Segment {
+                    start: (8, 0),
+                    end: (10, 2),
+                    steps: 8
+                },
+                Segment {
+                    start: (10, 2),
+                    end: (7, -1),
+                    steps: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_wire("R8,X5"),
+            Err(ParseError::UnknownDirection {
+                index: 1,
+                instruction: "X5".to_string()
+            })
+        );
+        assert_eq!(
+            parse_wire("R8,U5,L"),
+            Err(ParseError::InvalidLength {
+                index: 2,
+                instruction: "L".to_string()
+            })
+        );
+        assert_eq!(
+            parse_wire("D-3").unwrap_err().to_string(),
+            "Instruction 0 (\"D-3\") has an invalid length"
+        );
+        assert_eq!(
+            parse_wires("R8\n\nU5\nL5,Q"),
+            Err(LoadError {
+                wire: 2,
+                error: ParseError::UnknownDirection {
+                    index: 1,
+                    instruction: "Q".to_string()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_load() {
+        let file = std::env::temp_dir().join(format!("day-3-wires-{}.txt", std::process::id()));
+        std::fs::write(&file, "R8,U5,L5,D3\nU7,R6,D4,L4\n").unwrap();
+        let wires = load(&[file.to_str().unwrap(), "NE3"]).unwrap();
+        assert_eq!(wires.len(), 3);
+        assert_eq!(wires[2], parse_wire("NE3").unwrap());
+        assert_eq!(
+            load(&[file.to_str().unwrap(), "U2", "R2,U?"])
+                .unwrap_err()
+                .to_string(),
+            "Wire 3: Instruction 1 (\"U?\") has an invalid length"
+        );
+        std::fs::remove_file(&file).unwrap();
+        // A missing file is reported as such rather than read as a wire.
+        let missing = file.to_str().unwrap();
+        assert!(load(&[missing])
+            .unwrap_err()
+            .to_string()
+            .starts_with(&format!("{}: ", missing)));
+        assert!(load(&["wires.txt", "R8"])
+            .unwrap_err()
+            .to_string()
+            .starts_with("wires.txt: "));
+    }
+}
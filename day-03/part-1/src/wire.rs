@@ -0,0 +1,703 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+pub type Point = (i64, i64);
+
+// A straight run of wire. Wires are stored as segments rather than every cell
+// they cover so that arbitrarily long runs cost nothing extra.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub start: Point,
+    pub end: Point,
+    // Steps taken along the wire before reaching the start.
+    pub steps: i64,
+}
+
+impl Segment {
+    pub fn is_horizontal(&self) -> bool {
+        self.start.1 == self.end.1
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.start.0 != self.end.0 && self.start.1 != self.end.1
+    }
+
+    // One step along the segment.
+    pub fn direction(&self) -> Point {
+        (
+            (self.end.0 - self.start.0).signum(),
+            (self.end.1 - self.start.1).signum(),
+        )
+    }
+
+    pub fn length(&self) -> i64 {
+        (self.end.0 - self.start.0)
+            .abs()
+            .max((self.end.1 - self.start.1).abs())
+    }
+
+    pub fn steps_to(&self, point: Point) -> i64 {
+        self.steps
+            + (point.0 - self.start.0)
+                .abs()
+                .max((point.1 - self.start.1).abs())
+    }
+
+    fn x_range(&self) -> (i64, i64) {
+        (self.start.0.min(self.end.0), self.start.0.max(self.end.0))
+    }
+
+    fn y_range(&self) -> (i64, i64) {
+        (self.start.1.min(self.end.1), self.start.1.max(self.end.1))
+    }
+
+    // The range a horizontal or vertical segment covers along its line.
+    fn along(&self) -> (i64, i64) {
+        if self.is_horizontal() {
+            self.x_range()
+        } else {
+            self.y_range()
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Wire {
+    pub segments: Vec<Segment>,
+}
+
+impl Wire {
+    // Each move is a unit direction and how many times to take it. Moves of
+    // length zero don't add a segment.
+    pub fn from_moves<I: IntoIterator<Item = (Point, i64)>>(moves: I) -> Self {
+        let mut segments = vec![];
+        let mut position = (0, 0);
+        let mut steps = 0;
+        for ((dx, dy), len) in moves {
+            if len == 0 {
+                continue;
+            }
+            let end = (position.0 + dx * len, position.1 + dy * len);
+            segments.push(Segment {
+                start: position,
+                end,
+                steps,
+            });
+            position = end;
+            steps += len;
+        }
+        Wire { segments }
+    }
+
+    pub fn len(&self) -> i64 {
+        self.segments.iter().map(Segment::length).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+// A point where two or more wires meet, with the fewest steps each of those
+// wires takes to get there.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Crossing {
+    pub point: Point,
+    pub steps: BTreeMap<usize, i64>,
+}
+
+impl Crossing {
+    pub fn distance(&self) -> i64 {
+        self.point.0.abs() + self.point.1.abs()
+    }
+
+    pub fn combined_steps(&self) -> i64 {
+        self.steps.values().sum()
+    }
+
+    // The wires that meet here, in the order they were given.
+    pub fn wires(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps.keys().copied()
+    }
+
+    pub fn involves(&self, wire: usize) -> bool {
+        self.steps.contains_key(&wire)
+    }
+}
+
+impl Display for Crossing {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        let wires: Vec<String> = self.wires().map(|wire| wire.to_string()).collect();
+        write!(
+            f,
+            "({}, {}) crossed by wires {}",
+            self.point.0,
+            self.point.1,
+            wires.join(", ")
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Metric {
+    // Distance from the central port.
+    Manhattan,
+    // Total steps the wires take to get there.
+    Steps,
+}
+
+impl Metric {
+    // Only the steps of the given wires count, so a crossing can be measured
+    // for any pair of the wires that meet there.
+    pub fn measure(self, crossing: &Crossing, wires: &[usize]) -> i64 {
+        match self {
+            Metric::Manhattan => crossing.distance(),
+            Metric::Steps => wires
+                .iter()
+                .filter_map(|wire| crossing.steps.get(wire))
+                .sum(),
+        }
+    }
+}
+
+// The best crossing of the wires with at least `min_wires` wires meeting at
+// it, measuring it by all of the wires there.
+pub fn closest(wires: &[Wire], min_wires: usize, metric: Metric) -> Option<(Crossing, i64)> {
+    candidates(wires)
+        .into_iter()
+        .filter(|crossing| crossing.steps.len() >= min_wires)
+        .map(|crossing| {
+            let wires: Vec<usize> = crossing.wires().collect();
+            let value = metric.measure(&crossing, &wires);
+            (crossing, value)
+        })
+        .min_by_key(|(crossing, value)| (*value, crossing.point))
+}
+
+// The best crossing for every pair of wires that meet at all.
+pub fn pairwise(wires: &[Wire], metric: Metric) -> BTreeMap<(usize, usize), (Point, i64)> {
+    let mut best: BTreeMap<(usize, usize), (Point, i64)> = BTreeMap::new();
+    for crossing in candidates(wires) {
+        let wires: Vec<usize> = crossing.wires().collect();
+        for (index, &a) in wires.iter().enumerate() {
+            for &b in &wires[index + 1..] {
+                let value = metric.measure(&crossing, &[a, b]);
+                best.entry((a, b))
+                    .and_modify(|current| {
+                        if (value, crossing.point) < (current.1, current.0) {
+                            *current = (crossing.point, value);
+                        }
+                    })
+                    .or_insert((crossing.point, value));
+            }
+        }
+    }
+    best
+}
+
+// A run of lattice points two segments share, `len` steps of `step` from
+// `start`. A single point is a stretch of length zero.
+#[derive(Clone, Debug, PartialEq)]
+struct Stretch {
+    start: Point,
+    step: Point,
+    len: i64,
+}
+
+impl Stretch {
+    fn at(&self, along: i64) -> Point {
+        (
+            self.start.0 + along * self.step.0,
+            self.start.1 + along * self.step.1,
+        )
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..=self.len).map(move |along| self.at(along))
+    }
+
+    // The only points of the stretch that can be the best crossing. Each
+    // wire's steps rise or fall steadily along it, so the fewest steps are at
+    // one of its ends, and it comes closest to the central port at an end or
+    // where it crosses an axis. The port itself doesn't count, so the points
+    // either side of each axis stand in for it.
+    fn samples(&self) -> Vec<Point> {
+        let mut along = vec![0, self.len];
+        for (coordinate, step) in [(self.start.0, self.step.0), (self.start.1, self.step.1)] {
+            if step != 0 {
+                let axis = -coordinate * step;
+                along.extend(&[axis - 1, axis, axis + 1]);
+            }
+        }
+        let mut along: Vec<i64> = along
+            .into_iter()
+            .map(|along| along.clamp(0, self.len))
+            .collect();
+        along.sort_unstable();
+        along.dedup();
+        along.into_iter().map(|along| self.at(along)).collect()
+    }
+}
+
+#[derive(Default)]
+struct Found {
+    // Only sample shared stretches rather than recording every point. The
+    // best crossing is still found, but not every crossing is listed and the
+    // steps of those that are may not be the fewest.
+    sparse: bool,
+    points: HashMap<Point, BTreeMap<usize, i64>>,
+}
+
+impl Found {
+    fn add_stretch(&mut self, stretch: &Stretch, a: (usize, &Segment), b: (usize, &Segment)) {
+        if self.sparse {
+            for point in stretch.samples() {
+                self.add(point, a, b);
+            }
+        } else {
+            for point in stretch.points() {
+                self.add(point, a, b);
+            }
+        }
+    }
+
+    fn add(&mut self, point: Point, a: (usize, &Segment), b: (usize, &Segment)) {
+        // Every wire starts at the central port, which doesn't count.
+        if a.0 == b.0 || point == (0, 0) {
+            return;
+        }
+        let wires = self.points.entry(point).or_default();
+        for (wire, segment) in [a, b] {
+            let steps = segment.steps_to(point);
+            wires
+                .entry(wire)
+                .and_modify(|best| *best = (*best).min(steps))
+                .or_insert(steps);
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Event {
+    // Ordered so a vertical segment sees horizontal segments that start or
+    // end at the same x.
+    Insert,
+    Query,
+    Remove,
+}
+
+// Sweeps from left to right keeping the horizontal segments under the sweep
+// line ordered by y, so each vertical segment only looks at the horizontal
+// ones it actually crosses.
+fn perpendicular(segments: &[(usize, &Segment)], found: &mut Found) {
+    let mut events = vec![];
+    for (index, (_, segment)) in segments.iter().enumerate() {
+        if segment.is_horizontal() {
+            let (left, right) = segment.x_range();
+            events.push((left, Event::Insert, index));
+            events.push((right, Event::Remove, index));
+        } else {
+            events.push((segment.start.0, Event::Query, index));
+        }
+    }
+    events.sort_unstable();
+
+    let mut active: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+    for (x, event, index) in events {
+        let y = segments[index].1.start.1;
+        match event {
+            Event::Insert => active.entry(y).or_default().push(index),
+            Event::Remove => {
+                if let Some(indices) = active.get_mut(&y) {
+                    indices.retain(|&other| other != index);
+                    if indices.is_empty() {
+                        active.remove(&y);
+                    }
+                }
+            }
+            Event::Query => {
+                let (bottom, top) = segments[index].1.y_range();
+                for (&y, indices) in active.range(bottom..=top) {
+                    for &other in indices {
+                        found.add((x, y), segments[index], segments[other]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Segments on the same line can overlap, sharing a whole range of points.
+fn collinear(segments: &[(usize, &Segment)], found: &mut Found) {
+    let mut lines: HashMap<(bool, i64), Vec<usize>> = HashMap::new();
+    for (index, (_, segment)) in segments.iter().enumerate() {
+        let line = if segment.is_horizontal() {
+            segment.start.1
+        } else {
+            segment.start.0
+        };
+        lines
+            .entry((segment.is_horizontal(), line))
+            .or_default()
+            .push(index);
+    }
+    for ((horizontal, line), mut indices) in lines {
+        indices.sort_unstable_by_key(|&index| segments[index].1.along());
+        for (position, &index) in indices.iter().enumerate() {
+            let (_, end) = segments[index].1.along();
+            for &other in indices[position + 1..]
+                .iter()
+                .take_while(|&&other| segments[other].1.along().0 <= end)
+            {
+                let (start, other_end) = segments[other].1.along();
+                let stretch = Stretch {
+                    start: if horizontal {
+                        (start, line)
+                    } else {
+                        (line, start)
+                    },
+                    step: if horizontal { (1, 0) } else { (0, 1) },
+                    len: end.min(other_end) - start,
+                };
+                found.add_stretch(&stretch, segments[index], segments[other]);
+            }
+        }
+    }
+}
+
+fn cross(a: Point, b: Point) -> i64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+// Where two segments going in any of the eight directions meet, if they do.
+// Diagonals can cross between lattice points, in which case the wires never
+// actually touch.
+fn meet(a: &Segment, b: &Segment) -> Option<Stretch> {
+    let (da, db) = (a.direction(), b.direction());
+    let offset = (b.start.0 - a.start.0, b.start.1 - a.start.1);
+    let at = |t: i64| (a.start.0 + t * da.0, a.start.1 + t * da.1);
+    let det = cross(da, db);
+    if det == 0 {
+        // Parallel, so they only meet if they lie on the same line.
+        if cross(da, offset) != 0 {
+            return None;
+        }
+        let along = |point: Point| {
+            if da.0 != 0 {
+                (point.0 - a.start.0) * da.0
+            } else {
+                (point.1 - a.start.1) * da.1
+            }
+        };
+        let (first, second) = (along(b.start), along(b.end));
+        let start = first.min(second).max(0);
+        let end = first.max(second).min(a.length());
+        if start > end {
+            return None;
+        }
+        return Some(Stretch {
+            start: at(start),
+            step: da,
+            len: end - start,
+        });
+    }
+    // Solve a.start + t * da = b.start + u * db.
+    let (t, u) = (cross(offset, db), cross(offset, da));
+    if t % det != 0 || u % det != 0 {
+        return None;
+    }
+    let (t, u) = (t / det, u / det);
+    if (0..=a.length()).contains(&t) && (0..=b.length()).contains(&u) {
+        Some(Stretch {
+            start: at(t),
+            step: da,
+            len: 0,
+        })
+    } else {
+        None
+    }
+}
+
+// Diagonal segments don't fit the sweep, so they're checked against every
+// other segment. Fine as long as there aren't too many of them.
+fn diagonal(segments: &[(usize, &Segment)], found: &mut Found) {
+    for (index, &a) in segments.iter().enumerate() {
+        if !a.1.is_diagonal() {
+            continue;
+        }
+        for (other, &b) in segments.iter().enumerate() {
+            if b.1.is_diagonal() && other <= index {
+                continue;
+            }
+            if let Some(stretch) = meet(a.1, b.1) {
+                found.add_stretch(&stretch, a, b);
+            }
+        }
+    }
+}
+
+// Every point where at least two different wires meet, ordered by position,
+// with the fewest steps each wire takes to get there. Wires crossing
+// themselves don't count.
+pub fn crossings(wires: &[Wire]) -> Vec<Crossing> {
+    find(wires, false)
+}
+
+// Enough crossings to find the best ones, however long the wires run along
+// each other.
+fn candidates(wires: &[Wire]) -> Vec<Crossing> {
+    find(wires, true)
+}
+
+fn find(wires: &[Wire], sparse: bool) -> Vec<Crossing> {
+    let segments: Vec<(usize, &Segment)> = wires
+        .iter()
+        .enumerate()
+        .flat_map(|(wire, path)| path.segments.iter().map(move |segment| (wire, segment)))
+        .collect();
+    let straight: Vec<(usize, &Segment)> = segments
+        .iter()
+        .copied()
+        .filter(|(_, segment)| !segment.is_diagonal())
+        .collect();
+    let mut found = Found {
+        sparse,
+        ..Found::default()
+    };
+    perpendicular(&straight, &mut found);
+    collinear(&straight, &mut found);
+    diagonal(&segments, &mut found);
+    let mut crossings: Vec<Crossing> = found
+        .points
+        .into_iter()
+        .map(|(point, steps)| Crossing { point, steps })
+        .collect();
+    crossings.sort_unstable_by_key(|crossing| crossing.point);
+    crossings
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::parse_wire;
+
+    fn parse(wires: &[&str]) -> Vec<Wire> {
+        wires.iter().map(|wire| parse_wire(wire).unwrap()).collect()
+    }
+
+    fn points(crossings: &[Crossing]) -> Vec<(Point, i64)> {
+        crossings
+            .iter()
+            .map(|crossing| (crossing.point, crossing.combined_steps()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse_wire("R8,U5,L5,D3").unwrap().segments,
+            vec![
+                Segment {
+                    start: (0, 0),
+                    end: (8, 0),
+                    steps: 0
+                },
+                Segment {
+                    start: (8, 0),
+                    end: (8, 5),
+                    steps: 8
+                },
+                Segment {
+                    start: (8, 5),
+                    end: (3, 5),
+                    steps: 13
+                },
+                Segment {
+                    start: (3, 5),
+                    end: (3, 2),
+                    steps: 18
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crossings() {
+        assert_eq!(
+            points(&crossings(&parse(&["R8,U5,L5,D3", "U7,R6,D4,L4"]))),
+            vec![((3, 3), 40), ((6, 5), 30)]
+        );
+    }
+
+    #[test]
+    fn test_first_visit_steps() {
+        // The first wire passes (3, 0) after 3 steps and again after 15.
+        let crossings = crossings(&parse(&["R8,U1,L5,D3", "D1,R3,U1"]));
+        let crossing = crossings
+            .iter()
+            .find(|crossing| crossing.point == (3, 0))
+            .unwrap();
+        assert_eq!(crossing.steps, vec![(0, 3), (1, 5)].into_iter().collect());
+    }
+
+    #[test]
+    fn test_self_crossing_ignored() {
+        assert_eq!(crossings(&parse(&["R8,U1,L5,D3"])), vec![]);
+    }
+
+    #[test]
+    fn test_collinear_overlap() {
+        assert_eq!(
+            points(&crossings(&parse(&["R5", "U1,R2,D1,R2,U1"]))),
+            vec![((2, 0), 6), ((3, 0), 8), ((4, 0), 10)]
+        );
+        assert_eq!(
+            points(&crossings(&parse(&["U5", "R1,U3,L1,U4"]))),
+            vec![((0, 3), 8), ((0, 4), 10), ((0, 5), 12)]
+        );
+        assert_eq!(crossings(&parse(&["R10", "R10"])).len(), 10);
+    }
+
+    #[test]
+    fn test_second_visit_through_overlap() {
+        // The first wire runs along the second, then comes back down through
+        // (5, 0) after 17 steps.
+        let wires = parse(&["R10,U1,L5,D2", "R20"]);
+        let crossings = crossings(&wires);
+        assert_eq!(crossings.len(), 10);
+        let crossing = crossings
+            .iter()
+            .find(|crossing| crossing.point == (5, 0))
+            .unwrap();
+        assert_eq!(crossing.steps, vec![(0, 5), (1, 5)].into_iter().collect());
+        let (crossing, steps) = closest(&wires, 2, Metric::Steps).unwrap();
+        assert_eq!((crossing.point, steps), ((1, 0), 2));
+    }
+
+    #[test]
+    fn test_samples() {
+        let stretch = Stretch {
+            start: (-3, 1),
+            step: (1, -1),
+            len: 5,
+        };
+        assert_eq!(stretch.points().count(), 6);
+        assert_eq!(
+            stretch.samples(),
+            vec![(-3, 1), (-2, 0), (-1, -1), (0, -2), (1, -3), (2, -4)]
+        );
+        let stretch = Stretch {
+            start: (-1000, 0),
+            step: (1, 0),
+            len: 2000,
+        };
+        assert_eq!(
+            stretch.samples(),
+            vec![(-1000, 0), (-1, 0), (0, 0), (1, 0), (1000, 0)]
+        );
+        // The best crossing along a shared stretch is still found.
+        let wires = parse(&["L5,U1,R10", "R4,U1,L8"]);
+        let (crossing, distance) = closest(&wires, 2, Metric::Manhattan).unwrap();
+        assert_eq!((crossing.point, distance), ((0, 1), 1));
+    }
+
+    #[test]
+    fn test_many_wires() {
+        let crossings = crossings(&parse(&["R10", "U5,R5,D5", "D5,R5,U5"]));
+        assert_eq!(crossings.len(), 1);
+        assert_eq!(crossings[0].point, (5, 0));
+        assert_eq!(
+            crossings[0].steps,
+            vec![(0, 5), (1, 15), (2, 15)].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_closest() {
+        let wires = parse(&["R8,U5,L5,D3", "U7,R6,D4,L4"]);
+        let (crossing, distance) = closest(&wires, 2, Metric::Manhattan).unwrap();
+        assert_eq!((crossing.point, distance), ((3, 3), 6));
+        let (crossing, steps) = closest(&wires, 2, Metric::Steps).unwrap();
+        assert_eq!((crossing.point, steps), ((6, 5), 30));
+        assert_eq!(closest(&wires, 3, Metric::Manhattan), None);
+    }
+
+    #[test]
+    fn test_k_wires() {
+        let wires = parse(&["R10", "U5,R5,D5", "D5,R5,U5", "R2,U1"]);
+        let (crossing, distance) = closest(&wires, 2, Metric::Manhattan).unwrap();
+        assert_eq!((crossing.point, distance), ((1, 0), 1));
+        assert_eq!(crossing.wires().collect::<Vec<_>>(), vec![0, 3]);
+        let (crossing, distance) = closest(&wires, 3, Metric::Manhattan).unwrap();
+        assert_eq!((crossing.point, distance), ((5, 0), 5));
+        assert!(crossing.involves(2) && !crossing.involves(3));
+        assert_eq!(crossing.to_string(), "(5, 0) crossed by wires 0, 1, 2");
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let wires = parse(&["R10", "U5,R5,D5", "D5,R5,U5", "R2,U1"]);
+        let expected: BTreeMap<(usize, usize), (Point, i64)> = vec![
+            ((0, 1), ((5, 0), 20)),
+            ((0, 2), ((5, 0), 20)),
+            ((0, 3), ((1, 0), 2)),
+            ((1, 2), ((5, 0), 30)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(pairwise(&wires, Metric::Steps), expected);
+        assert_eq!(pairwise(&wires, Metric::Manhattan)[&(1, 2)], ((5, 0), 5));
+    }
+
+    #[test]
+    fn test_meet() {
+        let segment = |start, end| Segment {
+            start,
+            end,
+            steps: 0,
+        };
+        let meet = |a: &Segment, b: &Segment| {
+            meet(a, b).map_or(vec![], |stretch| stretch.points().collect::<Vec<_>>())
+        };
+        let diagonal = segment((0, 0), (4, 4));
+        assert_eq!(meet(&diagonal, &segment((3, 0), (3, 5))), vec![(3, 3)]);
+        assert_eq!(meet(&diagonal, &segment((0, 4), (4, 0))), vec![(2, 2)]);
+        // Crosses at (1.5, 1.5), between lattice points.
+        assert_eq!(meet(&diagonal, &segment((0, 3), (3, 0))), vec![]);
+        assert_eq!(
+            meet(&diagonal, &segment((5, 5), (2, 2))),
+            vec![(2, 2), (3, 3), (4, 4)]
+        );
+        assert_eq!(meet(&diagonal, &segment((1, 0), (5, 4))), vec![]);
+        assert_eq!(meet(&diagonal, &segment((5, 5), (6, 6))), vec![]);
+    }
+
+    #[test]
+    fn test_diagonal_crossings() {
+        assert_eq!(
+            points(&crossings(&parse(&["NE4", "R3,U5", "U4,SE4"]))),
+            vec![((2, 2), 8), ((3, 1), 11), ((3, 3), 9)]
+        );
+    }
+
+    #[test]
+    fn test_long_segments() {
+        let wires = parse(&["R2000000000,U2000000000", "U1000000000,R3000000000"]);
+        assert_eq!(
+            points(&crossings(&wires)),
+            vec![((2_000_000_000, 1_000_000_000), 6_000_000_000)]
+        );
+        // Overlapping along billions of points, which only the best crossing
+        // can be found for.
+        let wires = parse(&["R4000000000", "U1,R1000000000,D1,R2000000000"]);
+        let (crossing, steps) = closest(&wires, 2, Metric::Steps).unwrap();
+        assert_eq!((crossing.point, steps), ((1_000_000_000, 0), 2_000_000_002));
+        let (crossing, distance) = closest(&wires, 2, Metric::Manhattan).unwrap();
+        assert_eq!(
+            (crossing.point, distance),
+            ((1_000_000_000, 0), 1_000_000_000)
+        );
+        let wires = parse(&["NE2000000000", "R2,U2,NE1000000000"]);
+        assert_eq!(pairwise(&wires, Metric::Steps)[&(0, 1)], ((2, 2), 6));
+    }
+}
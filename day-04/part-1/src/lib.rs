@@ -0,0 +1,51 @@
+use std::convert::TryFrom;
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
+pub mod password;
+
+use self::password::{NonDecreasing, Password, RunAtLeast};
+
+// The input is the range of passwords to check, e.g. `357253-892942`.
+pub fn parse_range(input: &str) -> Result<(u64, u64), Box<dyn Error>> {
+    let mut bounds = input.trim().split('-');
+    match (bounds.next(), bounds.next(), bounds.next()) {
+        (Some(min), Some(max), None) => Ok((min.parse()?, max.parse()?)),
+        _ => Err(format!("Invalid range: {}", input.trim()).into()),
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let (min, max) = parse_range(input)?;
+    let password = Password::new().rule(NonDecreasing).rule(RunAtLeast(2));
+    Ok(Answer::from(u64::try_from(password.count(min..=max))?))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_range() {
+        assert_eq!(parse_range("357253-892942\n").unwrap(), (357_253, 892_942));
+        assert!(parse_range("357253").is_err());
+        assert!(parse_range("1-2-3").is_err());
+    }
+}
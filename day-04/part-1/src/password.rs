@@ -0,0 +1,440 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+// A rule reads a number one digit at a time, most significant first, keeping
+// whatever it needs to know in a small state. Rules only see the digits of the
+// number itself, never leading zeros.
+pub trait Rule {
+    fn start(&self) -> u32;
+    // None when no number starting with these digits can be valid.
+    fn step(&self, state: u32, digit: u8) -> Option<u32>;
+    fn accepts(&self, state: u32) -> bool;
+}
+
+pub struct NonDecreasing;
+
+impl Rule for NonDecreasing {
+    // The last digit plus one, so zero means there isn't one yet.
+    fn start(&self) -> u32 {
+        0
+    }
+
+    fn step(&self, last: u32, digit: u8) -> Option<u32> {
+        let digit = u32::from(digit) + 1;
+        if digit < last {
+            None
+        } else {
+            Some(digit)
+        }
+    }
+
+    fn accepts(&self, _: u32) -> bool {
+        true
+    }
+}
+
+// Tracks the current run of equal digits. Once a rule is satisfied the run no
+// longer matters, so every satisfied state is the same one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Run {
+    last: Option<u8>,
+    len: u32,
+    done: bool,
+}
+
+const DONE: u32 = 1 << 31;
+
+impl Run {
+    fn unpack(state: u32) -> Self {
+        Run {
+            last: match state & 0xf {
+                0 => None,
+                last => Some(last as u8 - 1),
+            },
+            len: (state >> 4) & 0xff,
+            done: state & DONE != 0,
+        }
+    }
+
+    fn pack(self) -> u32 {
+        if self.done {
+            return DONE;
+        }
+        self.last.map_or(0, |last| u32::from(last) + 1) | self.len.min(0xff) << 4
+    }
+
+    fn push(self, digit: u8) -> Self {
+        if self.last == Some(digit) {
+            Run {
+                len: self.len + 1,
+                ..self
+            }
+        } else {
+            Run {
+                last: Some(digit),
+                len: 1,
+                done: self.done,
+            }
+        }
+    }
+}
+
+// Some digit appears at least this many times in a row.
+pub struct RunAtLeast(pub u32);
+
+impl Rule for RunAtLeast {
+    fn start(&self) -> u32 {
+        0
+    }
+
+    fn step(&self, state: u32, digit: u8) -> Option<u32> {
+        let mut run = Run::unpack(state).push(digit);
+        run.done |= run.len >= self.0;
+        Some(run.pack())
+    }
+
+    fn accepts(&self, state: u32) -> bool {
+        Run::unpack(state).done
+    }
+}
+
+// Some digit appears exactly this many times in a row, not as part of a
+// longer run.
+pub struct RunExactly(pub u32);
+
+impl Rule for RunExactly {
+    fn start(&self) -> u32 {
+        0
+    }
+
+    fn step(&self, state: u32, digit: u8) -> Option<u32> {
+        let run = Run::unpack(state);
+        let ended = run.last.is_some_and(|last| last != digit) && run.len == self.0;
+        let mut next = run.push(digit);
+        next.done |= ended;
+        // Longer runs all fail the same way, so don't tell them apart.
+        next.len = next.len.min(self.0 + 1);
+        Some(next.pack())
+    }
+
+    fn accepts(&self, state: u32) -> bool {
+        let run = Run::unpack(state);
+        run.done || run.len == self.0
+    }
+}
+
+// A rule made from closures, for anything the others don't cover.
+pub struct Custom<S, A> {
+    pub start: u32,
+    pub step: S,
+    pub accepts: A,
+}
+
+impl<S, A> Rule for Custom<S, A>
+where
+    S: Fn(u32, u8) -> Option<u32>,
+    A: Fn(u32) -> bool,
+{
+    fn start(&self) -> u32 {
+        self.start
+    }
+
+    fn step(&self, state: u32, digit: u8) -> Option<u32> {
+        (self.step)(state, digit)
+    }
+
+    fn accepts(&self, state: u32) -> bool {
+        (self.accepts)(state)
+    }
+}
+
+fn digits(number: u64) -> Vec<u8> {
+    number
+        .to_string()
+        .bytes()
+        .map(|digit| digit - b'0')
+        .collect()
+}
+
+// Memoised counts of valid completions, keyed by how many digits are left to
+// choose and the state of every rule.
+type Memo = HashMap<(usize, Vec<u32>), u128>;
+
+// Counts numbers matching every rule using digit dynamic programming: numbers
+// that share a suffix length and rule states share a count, so ranges with
+// many digits cost about as much as short ones. Counts are u128 as there can be
+// more valid numbers in a range than a u64 holds.
+#[derive(Default)]
+pub struct Password {
+    rules: Vec<Box<dyn Rule>>,
+}
+
+impl Password {
+    pub fn new() -> Self {
+        Password::default()
+    }
+
+    pub fn rule<R: Rule + 'static>(mut self, rule: R) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    fn start(&self) -> Vec<u32> {
+        self.rules.iter().map(|rule| rule.start()).collect()
+    }
+
+    fn step(&self, states: &[u32], digit: u8) -> Option<Vec<u32>> {
+        self.rules
+            .iter()
+            .zip(states)
+            .map(|(rule, &state)| rule.step(state, digit))
+            .collect()
+    }
+
+    fn accepts(&self, states: &[u32]) -> bool {
+        self.rules
+            .iter()
+            .zip(states)
+            .all(|(rule, &state)| rule.accepts(state))
+    }
+
+    pub fn is_valid(&self, number: u64) -> bool {
+        digits(number)
+            .into_iter()
+            .try_fold(self.start(), |states, digit| self.step(&states, digit))
+            .is_some_and(|states| self.accepts(&states))
+    }
+
+    // Valid numbers of exactly `remaining` more digits following the states.
+    fn completions(&self, remaining: usize, states: &[u32], memo: &mut Memo) -> u128 {
+        if remaining == 0 {
+            return u128::from(self.accepts(states));
+        }
+        let key = (remaining, states.to_vec());
+        if let Some(&count) = memo.get(&key) {
+            return count;
+        }
+        let count = (0..=9)
+            .filter_map(|digit| self.step(states, digit))
+            .map(|next| self.completions(remaining - 1, &next, memo))
+            .sum();
+        memo.insert(key, count);
+        count
+    }
+
+    // Valid numbers from 0 to `max`.
+    fn count_to(&self, max: u64, memo: &mut Memo) -> u128 {
+        let digits = digits(max);
+        let len = digits.len();
+        // Shorter numbers can use any digits at all, as long as they don't
+        // start with a zero.
+        let mut count = u128::from(self.is_valid(0) && max > 0);
+        for shorter in 1..len {
+            for first in 1..=9 {
+                if let Some(states) = self.step(&self.start(), first) {
+                    count += self.completions(shorter - 1, &states, memo);
+                }
+            }
+        }
+        // Then numbers of the same length, which match the digits of `max` up
+        // to some position and are smaller at it.
+        let mut states = Some(self.start());
+        for (position, &limit) in digits.iter().enumerate() {
+            let current = match &states {
+                Some(current) => current.clone(),
+                None => break,
+            };
+            let lowest = if position == 0 { 1 } else { 0 };
+            for digit in lowest..limit {
+                if let Some(next) = self.step(&current, digit) {
+                    count += self.completions(len - position - 1, &next, memo);
+                }
+            }
+            states = self.step(&current, limit);
+        }
+        if states.is_some_and(|states| self.accepts(&states)) {
+            count += 1;
+        }
+        count
+    }
+
+    pub fn count(&self, range: RangeInclusive<u64>) -> u128 {
+        let (min, max) = range.into_inner();
+        if min > max {
+            return 0;
+        }
+        let mut memo = Memo::new();
+        let below = if min == 0 {
+            0
+        } else {
+            self.count_to(min - 1, &mut memo)
+        };
+        self.count_to(max, &mut memo) - below
+    }
+
+    // Every valid number in the range, in order. Only follows digits that can
+    // still lead to a valid number in range, so the work depends on how many
+    // there are rather than how wide the range is.
+    pub fn enumerate(&self, range: RangeInclusive<u64>) -> Vec<u64> {
+        let (min, max) = range.into_inner();
+        let mut found = vec![];
+        if min > max {
+            return found;
+        }
+        if min == 0 && self.is_valid(0) {
+            found.push(0);
+        }
+        let range = min..=max;
+        let mut memo = Memo::new();
+        for len in digits(min.max(1)).len()..=digits(max).len() {
+            for first in 1..=9 {
+                if let Some(states) = self.step(&self.start(), first) {
+                    let prefix = u64::from(first);
+                    self.search(len - 1, prefix, &states, &range, &mut memo, &mut found);
+                }
+            }
+        }
+        found
+    }
+
+    fn search(
+        &self,
+        remaining: usize,
+        prefix: u64,
+        states: &[u32],
+        range: &RangeInclusive<u64>,
+        memo: &mut Memo,
+        found: &mut Vec<u64>,
+    ) {
+        // Worked out in u128 as numbers starting with the prefix can be too
+        // big for a u64.
+        let scale = 10u128.pow(remaining as u32);
+        let lowest = u128::from(prefix) * scale;
+        let highest = lowest + scale - 1;
+        if highest < u128::from(*range.start())
+            || lowest > u128::from(*range.end())
+            || self.completions(remaining, states, memo) == 0
+        {
+            return;
+        }
+        if remaining == 0 {
+            found.push(prefix);
+            return;
+        }
+        for digit in 0..=9 {
+            // Past u64::MAX, so past the end of the range too.
+            let prefix = match prefix
+                .checked_mul(10)
+                .and_then(|prefix| prefix.checked_add(u64::from(digit)))
+            {
+                Some(prefix) => prefix,
+                None => break,
+            };
+            if let Some(next) = self.step(states, digit) {
+                self.search(remaining - 1, prefix, &next, range, memo, found);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn part_1() -> Password {
+        Password::new().rule(NonDecreasing).rule(RunAtLeast(2))
+    }
+
+    fn part_2() -> Password {
+        Password::new().rule(NonDecreasing).rule(RunExactly(2))
+    }
+
+    fn brute_force(password: &Password, range: RangeInclusive<u64>) -> Vec<u64> {
+        range.filter(|&number| password.is_valid(number)).collect()
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(part_1().is_valid(111_111));
+        assert!(!part_1().is_valid(223_450));
+        assert!(!part_1().is_valid(123_789));
+        assert!(part_2().is_valid(112_233));
+        assert!(!part_2().is_valid(123_444));
+        assert!(part_2().is_valid(111_122));
+    }
+
+    #[test]
+    fn test_count_matches_brute_force() {
+        for password in &[part_1(), part_2()] {
+            for &(min, max) in &[
+                (0, 0),
+                (0, 5000),
+                (7, 99),
+                (100, 100),
+                (345, 12_345),
+                (9, 8),
+            ] {
+                let expected = brute_force(password, min..=max);
+                assert_eq!(password.count(min..=max), expected.len() as u128);
+                assert_eq!(password.enumerate(min..=max), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        // Digit sum is a multiple of 7.
+        let password = Password::new().rule(Custom {
+            start: 0,
+            step: |sum, digit| Some((sum + u32::from(digit)) % 7),
+            accepts: |sum| sum == 0,
+        });
+        assert_eq!(password.enumerate(0..=30), brute_force(&password, 0..=30));
+        assert_eq!(password.enumerate(0..=30), vec![0, 7, 16, 25]);
+        assert_eq!(
+            password.count(0..=99_999),
+            brute_force(&password, 0..=99_999).len() as u128
+        );
+    }
+
+    #[test]
+    fn test_long_ranges() {
+        // Non-decreasing 15 digit numbers: choose 15 digits from 1-9 with
+        // repetition, C(23, 8).
+        let password = Password::new().rule(NonDecreasing);
+        assert_eq!(
+            password.count(100_000_000_000_000..=999_999_999_999_999),
+            490_314
+        );
+        let (min, split, max) = (1_000_000_000_000_000, 3_456_789_999_999_999, u64::MAX);
+        assert_eq!(
+            part_2().count(min..=max),
+            part_2().count(min..=split) + part_2().count(split + 1..=max)
+        );
+        assert_eq!(
+            part_1().enumerate(123_456_789_012_345..=123_456_789_111_111),
+            vec![]
+        );
+        assert_eq!(
+            part_1().enumerate(111_111_111_111_110..=111_111_111_111_112),
+            vec![111_111_111_111_111, 111_111_111_111_112]
+        );
+    }
+
+    #[test]
+    fn test_u64_max() {
+        let password = Password::new().rule(RunAtLeast(1));
+        let top = u64::MAX - 5..=u64::MAX;
+        assert_eq!(
+            password.enumerate(top.clone()),
+            top.clone().collect::<Vec<_>>()
+        );
+        assert_eq!(password.count(top), 6);
+        // One more than fits in a u64.
+        assert_eq!(Password::new().count(0..=u64::MAX), 1 << 64);
+        assert_eq!(
+            part_1().enumerate(u64::MAX - 10_000..=u64::MAX),
+            brute_force(&part_1(), u64::MAX - 10_000..=u64::MAX)
+        );
+    }
+}
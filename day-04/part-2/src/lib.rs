@@ -0,0 +1,28 @@
+use std::convert::TryFrom;
+use std::error::Error;
+
+use day_4_part_1::parse_range;
+use day_4_part_1::password::{NonDecreasing, Password, RunExactly};
+use solution::{Answer, Solution};
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let (min, max) = parse_range(input)?;
+    let password = Password::new().rule(NonDecreasing).rule(RunExactly(2));
+    Ok(Answer::from(u64::try_from(password.count(min..=max))?))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
@@ -0,0 +1,34 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+use vm::lang::run_program;
+use vm::loader::parse_text;
+
+// The air conditioner's system ID.
+const SYSTEM_ID: i64 = 1;
+
+// Every test output should be zero, followed by the diagnostic code.
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let (outputs, _) = run_program(&parse_text(input)?, &[SYSTEM_ID]);
+    match outputs.split_last() {
+        Some((&code, tests)) if tests.iter().all(|&test| test == 0) => Ok(Answer::from(code)),
+        Some(_) => Err(format!("Diagnostic tests failed: {:?}", outputs).into()),
+        None => Err("No diagnostic code produced".into()),
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
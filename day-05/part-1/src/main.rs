@@ -1,9 +1,14 @@
-use vm::io::create_stdio_vmio;
-use vm::lang::load_from_file;
+use std::fs::File;
+use std::io::prelude::*;
+
+use solution::input_path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut vm = load_from_file("./input.txt")?;
-    vm.io = create_stdio_vmio();
-    vm.last().unwrap();
+    let mut contents = String::new();
+    {
+        let mut file = File::open(input_path(5))?;
+        file.read_to_string(&mut contents)?;
+    }
+    println!("{}", day_5_part_1::solve(&contents)?);
     Ok(())
 }
@@ -0,0 +1,41 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
+pub mod orbits;
+pub mod render;
+
+use self::orbits::OrbitMap;
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(Answer::from(OrbitMap::parse(input)?.checksum()))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test() {
+        assert_eq!(
+            Answer::from(42),
+            solve("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L").unwrap()
+        )
+    }
+}
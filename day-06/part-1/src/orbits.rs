@@ -0,0 +1,516 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub const ROOT: &str = "COM";
+
+#[derive(Debug, PartialEq)]
+pub enum OrbitError {
+    Malformed {
+        line: usize,
+        text: String,
+    },
+    // `previous` is the line the body was first given a parent on.
+    DuplicateParent {
+        line: usize,
+        body: String,
+        parent: String,
+        previous: usize,
+    },
+    // Each body in the cycle is orbited by the next, and the first by the
+    // last. The line is the earliest orbit in the cycle.
+    Cycle {
+        line: usize,
+        bodies: Vec<String>,
+    },
+    MissingRoot,
+    // The line the body first appears on.
+    Unreachable {
+        line: usize,
+        body: String,
+    },
+}
+
+impl Display for OrbitError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            OrbitError::Malformed { line, text } => {
+                write!(f, "Line {}: expected A)B, found {:?}", line, text)
+            }
+            OrbitError::DuplicateParent {
+                line,
+                body,
+                parent,
+                previous,
+            } => write!(
+                f,
+                "Line {}: {} can't orbit {}, it already orbits something on line {}",
+                line, body, parent, previous
+            ),
+            OrbitError::Cycle { line, bodies } => write!(
+                f,
+                "Line {}: orbits form a cycle {})...){}",
+                line,
+                bodies.join(")"),
+                bodies[0]
+            ),
+            OrbitError::MissingRoot => write!(f, "Nothing is orbiting {}", ROOT),
+            OrbitError::Unreachable { line, body } => {
+                write!(f, "Line {}: {} doesn't lead back to {}", line, body, ROOT)
+            }
+        }
+    }
+}
+
+impl Error for OrbitError {}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub bodies: usize,
+    pub direct: usize,
+    pub indirect: usize,
+    pub max_depth: usize,
+    // Bodies nothing orbits.
+    pub leaves: usize,
+}
+
+// Bodies are stored by index, with the name of each kept for lookups.
+#[derive(Debug, Default)]
+pub struct OrbitMap {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    parent: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    // The line each body first appears on, and the one giving its parent.
+    line: Vec<usize>,
+    parent_line: Vec<usize>,
+    depth: Vec<usize>,
+    // Bodies in the order they're reached from the roots, parents first.
+    order: Vec<usize>,
+}
+
+impl OrbitMap {
+    pub fn parse(input: &str) -> Result<Self, OrbitError> {
+        let mut map = OrbitMap::default();
+        for (index, text) in input.lines().enumerate() {
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            let (parent, child) = match text.split(')').collect::<Vec<_>>()[..] {
+                [parent, child] if !parent.is_empty() && !child.is_empty() => (parent, child),
+                _ => {
+                    return Err(OrbitError::Malformed {
+                        line: index + 1,
+                        text: text.to_string(),
+                    })
+                }
+            };
+            let line = index + 1;
+            let parent = map.add(parent, line);
+            let child = map.add(child, line);
+            if map.parent[child].is_some() {
+                return Err(OrbitError::DuplicateParent {
+                    line,
+                    body: map.names[child].clone(),
+                    parent: map.names[parent].clone(),
+                    previous: map.parent_line[child],
+                });
+            }
+            map.parent[child] = Some(parent);
+            map.parent_line[child] = line;
+            map.children[parent].push(child);
+        }
+        map.measure();
+        map.validate()?;
+        Ok(map)
+    }
+
+    // Every body has at most one parent by now, so anything not reached from a
+    // root is stuck in or hanging off a cycle. Past that, the only root should
+    // be COM.
+    fn validate(&self) -> Result<(), OrbitError> {
+        let mut reached = vec![false; self.len()];
+        for &body in &self.order {
+            reached[body] = true;
+        }
+        if let Some(mut cycle) = (0..self.len())
+            .filter(|&body| !reached[body])
+            .map(|body| self.cycle_from(body))
+            .min_by_key(|cycle| cycle.iter().map(|&body| self.parent_line[body]).min())
+        {
+            // Parents first, starting from the earliest orbit.
+            cycle.reverse();
+            let earliest = (0..cycle.len())
+                .min_by_key(|&position| self.parent_line[cycle[position]])
+                .unwrap();
+            let len = cycle.len();
+            cycle.rotate_left((earliest + len - 1) % len);
+            let line = self.parent_line[cycle[1 % len]];
+            let bodies = cycle.iter().map(|&body| self.names[body].clone()).collect();
+            return Err(OrbitError::Cycle { line, bodies });
+        }
+        let root = match self.index.get(ROOT) {
+            Some(&root) if self.parent[root].is_none() => root,
+            _ => return Err(OrbitError::MissingRoot),
+        };
+        // Parents come before their children in the order, so one pass finds
+        // everything around COM.
+        let mut connected = vec![false; self.len()];
+        for &body in &self.order {
+            connected[body] = body == root || self.parent[body].is_some_and(|p| connected[p]);
+        }
+        match (0..self.len())
+            .filter(|&body| !connected[body])
+            .min_by_key(|&body| self.line[body])
+        {
+            Some(body) => Err(OrbitError::Unreachable {
+                line: self.line[body],
+                body: self.names[body].clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    // Follows parents from the body until one repeats, giving the bodies of the
+    // cycle in the order they were walked, each one's parent next.
+    fn cycle_from(&self, mut body: usize) -> Vec<usize> {
+        let mut seen = HashMap::new();
+        let mut walk = vec![];
+        while !seen.contains_key(&body) {
+            seen.insert(body, walk.len());
+            walk.push(body);
+            body = self.parent[body].unwrap();
+        }
+        walk.split_off(seen[&body])
+    }
+
+    fn add(&mut self, name: &str, line: usize) -> usize {
+        if let Some(&body) = self.index.get(name) {
+            return body;
+        }
+        let body = self.names.len();
+        self.names.push(name.to_string());
+        self.index.insert(name.to_string(), body);
+        self.parent.push(None);
+        self.children.push(vec![]);
+        self.line.push(line);
+        self.parent_line.push(0);
+        body
+    }
+
+    fn measure(&mut self) {
+        self.depth = vec![0; self.names.len()];
+        let mut queue: VecDeque<usize> = (0..self.names.len())
+            .filter(|&body| self.parent[body].is_none())
+            .collect();
+        while let Some(body) = queue.pop_front() {
+            self.order.push(body);
+            for &child in &self.children[body] {
+                self.depth[child] = self.depth[body] + 1;
+                queue.push_back(child);
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.index.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    pub fn parent(&self, name: &str) -> Option<&str> {
+        let body = self.parent[*self.index.get(name)?]?;
+        Some(&self.names[body])
+    }
+
+    pub fn children(&self, name: &str) -> Vec<&str> {
+        self.index.get(name).map_or(vec![], |&body| {
+            self.children[body]
+                .iter()
+                .map(|&child| self.names[child].as_str())
+                .collect()
+        })
+    }
+
+    // Bodies nothing else is orbiting, COM in a well formed map.
+    pub fn roots(&self) -> Vec<&str> {
+        (0..self.len())
+            .filter(|&body| self.parent[body].is_none())
+            .map(|body| self.names[body].as_str())
+            .collect()
+    }
+
+    pub fn depth(&self, name: &str) -> Option<usize> {
+        Some(self.depth[*self.index.get(name)?])
+    }
+
+    // Everything the body orbits, directly or not, nearest first.
+    pub fn ancestors(&self, name: &str) -> Vec<&str> {
+        let mut ancestors = vec![];
+        let mut current = self.index.get(name).copied();
+        while let Some(parent) = current.and_then(|body| self.parent[body]) {
+            ancestors.push(self.names[parent].as_str());
+            current = Some(parent);
+        }
+        ancestors
+    }
+
+    fn lca_index(&self, mut a: usize, mut b: usize) -> Option<usize> {
+        while self.depth[a] > self.depth[b] {
+            a = self.parent[a]?;
+        }
+        while self.depth[b] > self.depth[a] {
+            b = self.parent[b]?;
+        }
+        while a != b {
+            a = self.parent[a]?;
+            b = self.parent[b]?;
+        }
+        Some(a)
+    }
+
+    // The closest body both are in orbit around, or either one if the other
+    // orbits it. None if they aren't connected.
+    pub fn lca(&self, a: &str, b: &str) -> Option<&str> {
+        let body = self.lca_index(*self.index.get(a)?, *self.index.get(b)?)?;
+        Some(&self.names[body])
+    }
+
+    // Every body from `from` to `to`, both included.
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<&str>> {
+        let (from, to) = (*self.index.get(from)?, *self.index.get(to)?);
+        let lca = self.lca_index(from, to)?;
+        let climb = |mut body: usize| {
+            let mut bodies = vec![];
+            while body != lca {
+                bodies.push(self.names[body].as_str());
+                body = self.parent[body].unwrap();
+            }
+            bodies
+        };
+        let mut path = climb(from);
+        path.push(&self.names[lca]);
+        path.extend(climb(to).into_iter().rev());
+        Some(path)
+    }
+
+    // The number of orbits between two bodies.
+    pub fn distance(&self, from: &str, to: &str) -> Option<usize> {
+        Some(self.path(from, to)?.len() - 1)
+    }
+
+    // Orbital transfers needed to go from the body `from` is orbiting to the
+    // body `to` is orbiting, as for YOU and SAN.
+    pub fn transfers(&self, from: &str, to: &str) -> Option<usize> {
+        self.distance(self.parent(from)?, self.parent(to)?)
+    }
+
+    // Orbital transfers needed to go from the body `from` is orbiting until
+    // `from` is orbiting `to` itself.
+    pub fn transfers_to(&self, from: &str, to: &str) -> Option<usize> {
+        self.distance(self.parent(from)?, to)
+    }
+
+    // The total number of direct and indirect orbits.
+    pub fn checksum(&self) -> usize {
+        self.depth.iter().sum()
+    }
+
+    // Each body plus everything orbiting it, directly or not.
+    pub fn subtree_sizes(&self) -> HashMap<&str, usize> {
+        let mut sizes = vec![1; self.len()];
+        for &body in self.order.iter().rev() {
+            if let Some(parent) = self.parent[body] {
+                sizes[parent] += sizes[body];
+            }
+        }
+        self.names().zip(sizes).collect()
+    }
+
+    pub fn stats(&self) -> Stats {
+        let direct = self.parent.iter().filter(|parent| parent.is_some()).count();
+        Stats {
+            bodies: self.len(),
+            direct,
+            indirect: self.checksum() - direct,
+            max_depth: self.depth.iter().copied().max().unwrap_or(0),
+            leaves: self
+                .children
+                .iter()
+                .filter(|children| children.is_empty())
+                .count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L";
+
+    #[test]
+    fn test_checksum() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        assert_eq!(map.checksum(), 42);
+        assert_eq!(
+            map.stats(),
+            Stats {
+                bodies: 12,
+                direct: 11,
+                indirect: 31,
+                max_depth: 7,
+                leaves: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ancestors() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        assert_eq!(map.ancestors("COM"), Vec::<&str>::new());
+        assert_eq!(map.ancestors("I"), vec!["D", "C", "B", "COM"]);
+        assert_eq!(
+            map.ancestors("L"),
+            vec!["K", "J", "E", "D", "C", "B", "COM"]
+        );
+        assert_eq!(map.depth("L"), Some(7));
+        assert_eq!(map.roots(), vec!["COM"]);
+    }
+
+    #[test]
+    fn test_lca() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        assert_eq!(map.lca("L", "I"), Some("D"));
+        assert_eq!(map.lca("H", "F"), Some("B"));
+        assert_eq!(map.lca("D", "L"), Some("D"));
+        assert_eq!(map.lca("L", "L"), Some("L"));
+        assert_eq!(map.lca("L", "X"), None);
+    }
+
+    #[test]
+    fn test_path() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        assert_eq!(map.path("L", "I"), Some(vec!["L", "K", "J", "E", "D", "I"]));
+        assert_eq!(map.path("D", "F"), Some(vec!["D", "E", "F"]));
+        assert_eq!(map.path("F", "F"), Some(vec!["F"]));
+        assert_eq!(map.distance("H", "F"), Some(6));
+    }
+
+    #[test]
+    fn test_transfers() {
+        let map = OrbitMap::parse(&format!("{}\nK)YOU\nI)SAN", EXAMPLE)).unwrap();
+        assert_eq!(map.transfers("YOU", "SAN"), Some(4));
+        // From K, which L orbits, to D, which I orbits.
+        assert_eq!(map.transfers("L", "I"), Some(3));
+        assert_eq!(map.transfers("COM", "I"), None);
+        // From K until L orbits I.
+        assert_eq!(map.transfers_to("L", "I"), Some(4));
+        assert_eq!(map.transfers_to("YOU", "SAN"), Some(5));
+        assert_eq!(map.transfers_to("COM", "I"), None);
+    }
+
+    #[test]
+    fn test_subtree_sizes() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        let sizes = map.subtree_sizes();
+        assert_eq!(sizes["COM"], 12);
+        assert_eq!(sizes["D"], 7);
+        assert_eq!(sizes["G"], 2);
+        assert_eq!(sizes["L"], 1);
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(
+            OrbitMap::parse("COM)B\nB-C").unwrap_err(),
+            OrbitError::Malformed {
+                line: 2,
+                text: "B-C".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicate_parent() {
+        let error = OrbitMap::parse("COM)A\nCOM)B\n\nA)C\nB)C").unwrap_err();
+        assert_eq!(
+            error,
+            OrbitError::DuplicateParent {
+                line: 5,
+                body: "C".to_string(),
+                parent: "B".to_string(),
+                previous: 4,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Line 5: C can't orbit B, it already orbits something on line 4"
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let error = OrbitMap::parse("COM)A\nC)B\nA)X\nB)D\nD)C").unwrap_err();
+        assert_eq!(
+            error,
+            OrbitError::Cycle {
+                line: 2,
+                bodies: vec!["C".to_string(), "B".to_string(), "D".to_string()],
+            }
+        );
+        assert_eq!(error.to_string(), "Line 2: orbits form a cycle C)B)D)...)C");
+        assert_eq!(
+            OrbitMap::parse("COM)A\nB)B").unwrap_err(),
+            OrbitError::Cycle {
+                line: 2,
+                bodies: vec!["B".to_string()],
+            }
+        );
+        // Bodies hanging off a cycle still point to it.
+        assert_eq!(
+            OrbitMap::parse("X)Y\nCOM)A\nY)Z\nZ)X").unwrap_err(),
+            OrbitError::Cycle {
+                line: 1,
+                bodies: vec!["X".to_string(), "Y".to_string(), "Z".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_root() {
+        assert_eq!(
+            OrbitMap::parse("A)B\nB)C").unwrap_err(),
+            OrbitError::MissingRoot
+        );
+        assert_eq!(
+            OrbitMap::parse("A)COM\nCOM)B").unwrap_err(),
+            OrbitError::MissingRoot
+        );
+        assert_eq!(OrbitMap::parse("").unwrap_err(), OrbitError::MissingRoot);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let error = OrbitMap::parse("COM)A\nA)B\nY)Z\nX)Y").unwrap_err();
+        assert_eq!(
+            error,
+            OrbitError::Unreachable {
+                line: 3,
+                body: "Y".to_string(),
+            }
+        );
+        assert_eq!(error.to_string(), "Line 3: Y doesn't lead back to COM");
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::orbits::OrbitMap;
+
+// A body, or a chain of bodies each orbited by exactly one other, drawn as a
+// single node.
+struct Node<'a> {
+    bodies: Vec<&'a str>,
+    highlighted: bool,
+}
+
+impl<'a> Node<'a> {
+    fn id(&self) -> String {
+        match &self.bodies[..] {
+            [body] => body.to_string(),
+            [first, .., last] => format!("{}..{}", first, last),
+            [] => unreachable!(),
+        }
+    }
+
+    fn label(&self) -> String {
+        match self.bodies.len() {
+            1 => self.id(),
+            len => format!("{} ({} bodies)", self.id(), len),
+        }
+    }
+
+    fn last(&self) -> &'a str {
+        self.bodies[self.bodies.len() - 1]
+    }
+}
+
+// Draws an orbit map for debugging, optionally highlighting the path between
+// two bodies and collapsing chains of bodies that only one thing orbits.
+pub struct Render<'a> {
+    map: &'a OrbitMap,
+    ends: Vec<&'a str>,
+    path: HashSet<&'a str>,
+    collapse: Option<usize>,
+}
+
+impl<'a> Render<'a> {
+    pub fn new(map: &'a OrbitMap) -> Self {
+        Render {
+            map,
+            ends: vec![],
+            path: HashSet::new(),
+            collapse: None,
+        }
+    }
+
+    // Marks every body from `from` to `to`. Nothing is marked if they aren't
+    // connected.
+    pub fn highlight(mut self, from: &'a str, to: &'a str) -> Self {
+        self.ends = vec![from, to];
+        self.path = self
+            .map
+            .path(from, to)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        self
+    }
+
+    // Draws chains of at least `min` bodies as one node. The ends of a
+    // highlighted path are always drawn on their own.
+    pub fn collapse(mut self, min: usize) -> Self {
+        self.collapse = Some(min.max(2));
+        self
+    }
+
+    fn collapsible(&self, body: &str) -> bool {
+        self.map.parent(body).is_some()
+            && self.map.children(body).len() == 1
+            && !self.ends.contains(&body)
+    }
+
+    fn node(&self, body: &'a str) -> Node<'a> {
+        let mut bodies = vec![body];
+        if let Some(min) = self.collapse {
+            let mut chain = vec![];
+            let mut current = body;
+            while self.collapsible(current) {
+                chain.push(current);
+                current = self.map.children(current)[0];
+            }
+            if chain.len() >= min {
+                bodies = chain;
+            }
+        }
+        // A chain is either all on the path or all off it, as the path can
+        // only turn at a body with two children or at one of its ends.
+        let highlighted = self.path.contains(body);
+        Node {
+            bodies,
+            highlighted,
+        }
+    }
+
+    fn children(&self, node: &Node<'a>) -> Vec<Node<'a>> {
+        self.map
+            .children(node.last())
+            .into_iter()
+            .map(|child| self.node(child))
+            .collect()
+    }
+
+    // An indented tree, one body per line, with highlighted bodies marked *.
+    pub fn text(&self) -> String {
+        let mut out = String::new();
+        for root in self.map.roots() {
+            let mut stack = vec![(self.node(root), String::new(), String::new())];
+            while let Some((node, branch, indent)) = stack.pop() {
+                let mark = if node.highlighted { " *" } else { "" };
+                writeln!(out, "{}{}{}", branch, node.label(), mark).unwrap();
+                let children = self.children(&node);
+                let count = children.len();
+                // Pushed in reverse so they come off the stack in order.
+                for (position, child) in children.into_iter().enumerate().rev() {
+                    let last = position + 1 == count;
+                    stack.push((
+                        child,
+                        format!("{}{}", indent, if last { "`-- " } else { "+-- " }),
+                        format!("{}{}", indent, if last { "    " } else { "|   " }),
+                    ));
+                }
+            }
+        }
+        out
+    }
+
+    // A Graphviz digraph with an edge from each body to the ones orbiting it.
+    pub fn dot(&self) -> String {
+        let mut out = String::from("digraph orbits {\n");
+        let mut stack: Vec<_> = self
+            .map
+            .roots()
+            .into_iter()
+            .map(|root| self.node(root))
+            .collect();
+        stack.reverse();
+        while let Some(node) = stack.pop() {
+            let mut attributes = vec![];
+            if node.bodies.len() > 1 {
+                attributes.push(format!("label={}", quote(&node.label())));
+                attributes.push("shape=box".to_string());
+            }
+            if node.highlighted {
+                attributes.push(HIGHLIGHT.to_string());
+            }
+            if !attributes.is_empty() {
+                writeln!(
+                    out,
+                    "    {} [{}];",
+                    quote(&node.id()),
+                    attributes.join(", ")
+                )
+                .unwrap();
+            }
+            let children = self.children(&node);
+            for child in &children {
+                let highlight = if node.highlighted && child.highlighted {
+                    format!(" [{}]", HIGHLIGHT)
+                } else {
+                    String::new()
+                };
+                writeln!(
+                    out,
+                    "    {} -> {}{};",
+                    quote(&node.id()),
+                    quote(&child.id()),
+                    highlight
+                )
+                .unwrap();
+            }
+            stack.extend(children.into_iter().rev());
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+const HIGHLIGHT: &str = "color=red, penwidth=2";
+
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN";
+
+    #[test]
+    fn test_text() {
+        let map = OrbitMap::parse("COM)B\nB)C\nB)D\nC)E").unwrap();
+        assert_eq!(
+            Render::new(&map).text(),
+            "COM\n`-- B\n    +-- C\n    |   `-- E\n    `-- D\n"
+        );
+    }
+
+    #[test]
+    fn test_text_highlight() {
+        let map = OrbitMap::parse(EXAMPLE).unwrap();
+        assert_eq!(
+            Render::new(&map).highlight("YOU", "SAN").text(),
+            [
+                "COM",
+                "`-- B",
+                "    +-- C",
+                "    |   `-- D *",
+                "    |       +-- E *",
+                "    |       |   +-- F",
+                "    |       |   `-- J *",
+                "    |       |       `-- K *",
+                "    |       |           +-- L",
+                "    |       |           `-- YOU *",
+                "    |       `-- I *",
+                "    |           `-- SAN *",
+                "    `-- G",
+                "        `-- H",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_collapse() {
+        let map = OrbitMap::parse("COM)A\nA)B\nB)C\nC)D\nD)E\nE)F\nE)G").unwrap();
+        assert_eq!(
+            Render::new(&map).collapse(3).text(),
+            "COM\n`-- A..D (4 bodies)\n    `-- E\n        +-- F\n        `-- G\n"
+        );
+        assert_eq!(
+            Render::new(&map).highlight("COM", "G").collapse(3).text(),
+            "COM *\n`-- A..D (4 bodies) *\n    `-- E *\n        +-- F\n        `-- G *\n"
+        );
+        assert_eq!(Render::new(&map).collapse(6).text().lines().count(), 8);
+        // The ends of the path stay visible.
+        assert_eq!(
+            Render::new(&map).highlight("C", "G").collapse(2).text(),
+            [
+                "COM",
+                "`-- A..B (2 bodies)",
+                "    `-- C *",
+                "        `-- D *",
+                "            `-- E *",
+                "                +-- F",
+                "                `-- G *",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_dot() {
+        let map = OrbitMap::parse("COM)A\nA)B\nB)C\nB)YOU\nA)SAN").unwrap();
+        assert_eq!(
+            Render::new(&map).highlight("YOU", "SAN").dot(),
+            [
+                "digraph orbits {",
+                "    \"COM\" -> \"A\";",
+                "    \"A\" [color=red, penwidth=2];",
+                "    \"A\" -> \"B\" [color=red, penwidth=2];",
+                "    \"A\" -> \"SAN\" [color=red, penwidth=2];",
+                "    \"B\" [color=red, penwidth=2];",
+                "    \"B\" -> \"C\";",
+                "    \"B\" -> \"YOU\" [color=red, penwidth=2];",
+                "    \"YOU\" [color=red, penwidth=2];",
+                "    \"SAN\" [color=red, penwidth=2];",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+        let map = OrbitMap::parse("COM)A\nA)B\nB)C").unwrap();
+        assert_eq!(
+            Render::new(&map).collapse(2).dot(),
+            "digraph orbits {\n    \"COM\" -> \"A..B\";\n    \"A..B\" [label=\"A..B (2 bodies)\", shape=box];\n    \"A..B\" -> \"C\";\n}\n"
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::error::Error;
+
+use day_6_part_1::orbits::OrbitMap;
+use solution::{Answer, Solution};
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let map = OrbitMap::parse(input)?;
+    let transfers = map
+        .transfers("YOU", "SAN")
+        .ok_or("No way to get from YOU to SAN")?;
+    Ok(Answer::from(transfers))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ancestors() {
+        let map =
+            OrbitMap::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L").unwrap();
+        let expected = vec![
+            ("B", vec!["COM"]),
+            ("C", vec!["B", "COM"]),
+            ("D", vec!["C", "B", "COM"]),
+            ("E", vec!["D", "C", "B", "COM"]),
+            ("F", vec!["E", "D", "C", "B", "COM"]),
+            ("G", vec!["B", "COM"]),
+            ("H", vec!["G", "B", "COM"]),
+            ("I", vec!["D", "C", "B", "COM"]),
+            ("J", vec!["E", "D", "C", "B", "COM"]),
+            ("K", vec!["J", "E", "D", "C", "B", "COM"]),
+            ("L", vec!["K", "J", "E", "D", "C", "B", "COM"]),
+        ];
+        for (body, ancestors) in expected {
+            assert_eq!(map.ancestors(body), ancestors, "{}", body);
+        }
+    }
+
+    #[test]
+    fn test_get_shortest_path() {
+        assert_eq!(
+            solve("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN").unwrap(),
+            Answer::from(4)
+        );
+    }
+
+    #[test]
+    fn test_get_shortest_path2() {
+        let map =
+            OrbitMap::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L").unwrap();
+
+        // Moving L from K until it orbits I.
+        assert_eq!(map.transfers_to("L", "I"), Some(4));
+        assert_eq!(map.path("K", "I"), Some(vec!["K", "J", "E", "D", "I"]));
+        // Whereas to orbit D, the body I orbits, takes one fewer.
+        assert_eq!(map.transfers("L", "I"), Some(3));
+    }
+}
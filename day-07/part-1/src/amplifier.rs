@@ -0,0 +1,294 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::from_fn;
+use std::rc::Rc;
+
+use rayon::prelude::*;
+use vm::lang::{get_ops, op_code_lookup};
+use vm::loader::{parse_text, LoadError};
+use vm::IntcodeVM;
+
+const INPUT: i64 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Topology {
+    // The last amplifier's output is the signal.
+    Linear,
+    // The last amplifier feeds back into the first until they all halt, and
+    // the last thing it sent is the signal.
+    Feedback,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ChainError {
+    PhaseCount { expected: usize, found: usize },
+    TooFewPhases { stages: usize, phases: usize },
+    // Waiting on input that's never going to come.
+    Stalled { stage: usize },
+    NoSignal,
+}
+
+impl Display for ChainError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ChainError::PhaseCount { expected, found } => {
+                write!(f, "Expected {} phases, found {}", expected, found)
+            }
+            ChainError::TooFewPhases { stages, phases } => write!(
+                f,
+                "Can't give {} amplifiers different phases from only {}",
+                stages, phases
+            ),
+            ChainError::Stalled { stage } => {
+                write!(f, "Amplifier {} is stuck waiting for input", stage)
+            }
+            ChainError::NoSignal => write!(f, "The last amplifier never sent a signal"),
+        }
+    }
+}
+
+impl Error for ChainError {}
+
+#[derive(Debug, PartialEq)]
+pub struct Optimum {
+    pub phases: Vec<i64>,
+    pub signal: i64,
+    // Every phase sequence tried along with its signal, in the order they
+    // were generated.
+    pub scores: Vec<(Vec<i64>, i64)>,
+}
+
+// Every copy of the program gets its phase followed by whatever the amplifier
+// before it outputs, with the first also given a 0 to start things off.
+pub struct Chain {
+    program: Vec<i64>,
+    stages: usize,
+    topology: Topology,
+}
+
+impl Chain {
+    pub fn new(program: Vec<i64>, stages: usize, topology: Topology) -> Self {
+        Chain {
+            program,
+            stages,
+            topology,
+        }
+    }
+
+    pub fn parse(program: &str, stages: usize, topology: Topology) -> Result<Self, LoadError> {
+        Ok(Chain::new(parse_text(program)?, stages, topology))
+    }
+
+    // Runs each amplifier in turn until it halts or needs input nobody has
+    // sent yet, and stops once none of them can go any further.
+    pub fn run(&self, phases: &[i64]) -> Result<i64, ChainError> {
+        if phases.len() != self.stages {
+            return Err(ChainError::PhaseCount {
+                expected: self.stages,
+                found: phases.len(),
+            });
+        }
+        let queues: Vec<_> = phases
+            .iter()
+            .map(|&phase| Rc::new(RefCell::new(VecDeque::from(vec![phase]))))
+            .collect();
+        if let Some(first) = queues.first() {
+            first.borrow_mut().push_back(0);
+        }
+        let mut vms: Vec<_> = queues
+            .iter()
+            .map(|queue| {
+                let queue = queue.clone();
+                let mut vm =
+                    IntcodeVM::create(self.program.clone(), get_ops(), &op_code_lookup, None, None);
+                vm.io.input = Some(Box::new(from_fn(move || queue.borrow_mut().pop_front())));
+                vm
+            })
+            .collect();
+        let mut halted = vec![false; self.stages];
+        let mut signal = None;
+        let mut progress = true;
+        while progress {
+            progress = false;
+            for (stage, vm) in vms.iter_mut().enumerate() {
+                while !halted[stage] {
+                    let op = op_code_lookup(vm.memory.memory[vm.memory.instruction_pointer]);
+                    if op == INPUT && queues[stage].borrow().is_empty() {
+                        break;
+                    }
+                    progress = true;
+                    match vm.step() {
+                        None => halted[stage] = true,
+                        Some(None) => {}
+                        Some(Some(value)) if stage + 1 < self.stages => {
+                            queues[stage + 1].borrow_mut().push_back(value)
+                        }
+                        Some(Some(value)) => {
+                            signal = Some(value);
+                            if self.topology == Topology::Feedback {
+                                queues[0].borrow_mut().push_back(value);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(stage) = halted.iter().position(|&halted| !halted) {
+            return Err(ChainError::Stalled { stage });
+        }
+        signal.ok_or(ChainError::NoSignal)
+    }
+
+    // Tries every way of giving the amplifiers different phases from the
+    // alphabet, in parallel. Ties go to whichever came first.
+    pub fn optimise(&self, alphabet: &[i64]) -> Result<Optimum, ChainError> {
+        if alphabet.len() < self.stages {
+            return Err(ChainError::TooFewPhases {
+                stages: self.stages,
+                phases: alphabet.len(),
+            });
+        }
+        let scores = permutations(alphabet, self.stages)
+            .into_par_iter()
+            .map(|phases| {
+                let signal = self.run(&phases)?;
+                Ok((phases, signal))
+            })
+            .collect::<Result<Vec<_>, ChainError>>()?;
+        let (phases, signal) = scores
+            .iter()
+            .fold(None, |best: Option<&(Vec<i64>, i64)>, score| match best {
+                Some(best) if best.1 >= score.1 => Some(best),
+                _ => Some(score),
+            })
+            .cloned()
+            .ok_or(ChainError::NoSignal)?;
+        Ok(Optimum {
+            phases,
+            signal,
+            scores,
+        })
+    }
+}
+
+// Every ordering of every `len` items from the alphabet: each combination of
+// positions in turn, ordered with Heap's algorithm.
+pub fn permutations(alphabet: &[i64], len: usize) -> Vec<Vec<i64>> {
+    let mut permutations = vec![];
+    if len > alphabet.len() {
+        return permutations;
+    }
+    let mut chosen: Vec<usize> = (0..len).collect();
+    loop {
+        let mut items: Vec<i64> = chosen.iter().map(|&index| alphabet[index]).collect();
+        heap(&mut items, &mut permutations);
+        // Move on to the next combination, bumping the last position that
+        // still has room and resetting the ones after it.
+        let last = match (0..len)
+            .rev()
+            .find(|&i| chosen[i] < alphabet.len() - len + i)
+        {
+            Some(last) => last,
+            None => return permutations,
+        };
+        chosen[last] += 1;
+        for i in last + 1..len {
+            chosen[i] = chosen[i - 1] + 1;
+        }
+    }
+}
+
+// Each permutation differs from the one before by a single swap.
+fn heap(items: &mut [i64], out: &mut Vec<Vec<i64>>) {
+    let mut counters = vec![0; items.len()];
+    out.push(items.to_vec());
+    let mut i = 1;
+    while i < items.len() {
+        if counters[i] < i {
+            if i % 2 == 0 {
+                items.swap(0, i);
+            } else {
+                items.swap(counters[i], i);
+            }
+            out.push(items.to_vec());
+            counters[i] += 1;
+            i = 1;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_permutations() {
+        let all = permutations(&[0, 1, 2, 3, 4], 5);
+        assert_eq!(all.len(), 120);
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 120);
+        assert_eq!(
+            permutations(&[1, 2, 3], 2),
+            vec![
+                vec![1, 2],
+                vec![2, 1],
+                vec![1, 3],
+                vec![3, 1],
+                vec![2, 3],
+                vec![3, 2]
+            ]
+        );
+        assert_eq!(permutations(&[1, 2], 0), vec![Vec::<i64>::new()]);
+        assert_eq!(permutations(&[1, 2], 3), Vec::<Vec<i64>>::new());
+    }
+
+    #[test]
+    fn test_run() {
+        // Adds its phase to its input.
+        let chain = Chain::parse("3,11,3,12,1,11,12,11,4,11,99,0,0", 3, Topology::Linear).unwrap();
+        assert_eq!(chain.run(&[1, 20, 300]), Ok(321));
+        assert_eq!(
+            chain.run(&[1, 2]),
+            Err(ChainError::PhaseCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_optimise() {
+        // Signal = 10 * input + phase, so the largest phases go first.
+        let chain = Chain::parse(
+            "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+            4,
+            Topology::Linear,
+        )
+        .unwrap();
+        let optimum = chain.optimise(&[7, 1, 3, 5, 8]).unwrap();
+        assert_eq!(optimum.phases, vec![8, 7, 5, 3]);
+        assert_eq!(optimum.signal, 8753);
+        assert_eq!(optimum.scores.len(), 120);
+        assert_eq!(
+            chain.optimise(&[1, 2]),
+            Err(ChainError::TooFewPhases {
+                stages: 4,
+                phases: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        // Wants two inputs but only ever gets one.
+        let chain = Chain::parse("3,0,3,0,3,0,4,0,99", 2, Topology::Linear).unwrap();
+        assert_eq!(chain.run(&[1, 2]), Err(ChainError::Stalled { stage: 0 }));
+        let chain = Chain::parse("3,0,3,0,99", 1, Topology::Linear).unwrap();
+        assert_eq!(chain.run(&[1]), Err(ChainError::NoSignal));
+    }
+}
@@ -0,0 +1,67 @@
+use solution::{Answer, Solution};
+
+pub mod amplifier;
+
+use self::amplifier::{Chain, Topology};
+
+type StandardError<T> = Result<T, Box<dyn std::error::Error>>;
+
+const PHASES: [i64; 5] = [0, 1, 2, 3, 4];
+
+fn get_optimal_phase(program: &str) -> StandardError<(i64, Vec<i64>)> {
+    let optimum = Chain::parse(program, PHASES.len(), Topology::Linear)?.optimise(&PHASES)?;
+    Ok((optimum.signal, optimum.phases))
+}
+
+pub fn solve(input: &str) -> StandardError<Answer> {
+    let (max, _) = get_optimal_phase(input.trim())?;
+    Ok(Answer::from(max))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> StandardError<Answer> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_1() {
+        assert_eq!(
+            get_optimal_phase("3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0").unwrap(),
+            (43210, vec![4, 3, 2, 1, 0]),
+        )
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(
+            get_optimal_phase(
+                "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0"
+            )
+            .unwrap(),
+            (54321, vec![0, 1, 2, 3, 4]),
+        )
+    }
+
+    #[test]
+    fn example_3() {
+        assert_eq!(
+            get_optimal_phase("3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0").unwrap(),
+            (65210, vec![1, 0, 4, 3, 2]),
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use day_7_part_1::amplifier::{Chain, Topology};
+use solution::{Answer, Solution};
+
+type StandardError<T> = Result<T, Box<dyn std::error::Error>>;
+
+const PHASES: [i64; 5] = [5, 6, 7, 8, 9];
+
+fn get_optimal_phase(program: &str) -> StandardError<(i64, Vec<i64>)> {
+    let optimum = Chain::parse(program, PHASES.len(), Topology::Feedback)?.optimise(&PHASES)?;
+    Ok((optimum.signal, optimum.phases))
+}
+
+pub fn solve(input: &str) -> StandardError<Answer> {
+    let (max, _) = get_optimal_phase(input.trim())?;
+    Ok(Answer::from(max))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        7
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> StandardError<Answer> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_1() {
+        assert_eq!(
+            get_optimal_phase("3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5").unwrap(),
+            (139_629_729, vec![9, 8, 7, 6, 5]),
+        )
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(
+            get_optimal_phase("3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10").unwrap(),
+            (18_216, vec![9, 7, 8, 5, 6]),
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
+pub mod sif;
+
+use self::sif::{Hint, Sif};
+
+pub const WIDTH: usize = 25;
+pub const HEIGHT: usize = 6;
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(Answer::from(
+        Sif::parse(input, Hint::size(WIDTH, HEIGHT))?.checksum(),
+    ))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
@@ -0,0 +1,708 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use grid::{Blend, LayerPalette};
+
+pub const BLACK: u8 = 0;
+pub const WHITE: u8 = 1;
+pub const TRANSPARENT: u8 = 2;
+
+#[derive(Debug, PartialEq)]
+pub enum SifError {
+    Empty,
+    // Positions count from zero, ignoring surrounding whitespace.
+    InvalidDigit {
+        position: usize,
+        found: char,
+    },
+    // The data doesn't split into whole layers of this size.
+    LayerSize {
+        len: usize,
+        width: usize,
+        height: usize,
+    },
+    NoDimensions {
+        len: usize,
+    },
+    Ambiguous {
+        candidates: Vec<(usize, usize)>,
+    },
+    BitmapSize {
+        expected: usize,
+        found: usize,
+    },
+    // Layers and positions within them count from zero.
+    NotInPalette {
+        layer: usize,
+        position: usize,
+        value: u8,
+    },
+    // Only values up to 9 can be written back out as digits.
+    NotADigit {
+        layer: usize,
+        position: usize,
+        value: u8,
+    },
+    NoLayer {
+        layer: usize,
+    },
+    Pnm {
+        reason: String,
+    },
+}
+
+impl Display for SifError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            SifError::Empty => write!(f, "The image has no data"),
+            SifError::InvalidDigit { position, found } => {
+                write!(
+                    f,
+                    "Position {}: expected a digit, found {:?}",
+                    position, found
+                )
+            }
+            SifError::LayerSize { len, width, height } => write!(
+                f,
+                "{} digits don't make whole {}x{} layers",
+                len, width, height
+            ),
+            SifError::NoDimensions { len } => {
+                write!(f, "No dimensions fit the hint for {} digits", len)
+            }
+            SifError::Ambiguous { candidates } => write!(
+                f,
+                "Could be any of {}",
+                candidates
+                    .iter()
+                    .map(|(width, height)| format!("{}x{}", width, height))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            SifError::BitmapSize { expected, found } => {
+                write!(f, "Expected {} pixels, found {}", expected, found)
+            }
+            SifError::NotInPalette {
+                layer,
+                position,
+                value,
+            } => write!(
+                f,
+                "Layer {} position {}: {} isn't in the palette",
+                layer, position, value
+            ),
+            SifError::NotADigit {
+                layer,
+                position,
+                value,
+            } => write!(
+                f,
+                "Layer {} position {}: {} can't be written as a digit",
+                layer, position, value
+            ),
+            SifError::NoLayer { layer } => write!(f, "There's no layer {}", layer),
+            SifError::Pnm { reason } => write!(f, "Invalid PBM/PGM: {}", reason),
+        }
+    }
+}
+
+impl Error for SifError {}
+
+// What's known about an image's shape. Anything left out is inferred, as long
+// as only one shape fits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Hint {
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub layers: Option<usize>,
+}
+
+impl Hint {
+    pub fn size(width: usize, height: usize) -> Self {
+        Hint {
+            width: Some(width),
+            height: Some(height),
+            layers: None,
+        }
+    }
+
+    // Every width and height that splits `len` digits into whole layers and
+    // agrees with the hint, narrowest first.
+    pub fn candidates(&self, len: usize) -> Vec<(usize, usize)> {
+        (1..=len)
+            .flat_map(|width| (1..=len / width).map(move |height| (width, height)))
+            .filter(|&(width, height)| len.is_multiple_of(width * height))
+            .filter(|&(width, height)| {
+                self.width.is_none_or(|hint| hint == width)
+                    && self.height.is_none_or(|hint| hint == height)
+                    && self
+                        .layers
+                        .is_none_or(|hint| hint.checked_mul(width * height) == Some(len))
+            })
+            .collect()
+    }
+
+    pub fn infer(&self, len: usize) -> Result<(usize, usize), SifError> {
+        if let Hint {
+            width: Some(width),
+            height: Some(height),
+            ..
+        } = *self
+        {
+            match width.checked_mul(height) {
+                Some(size) if size > 0 && len.is_multiple_of(size) => {}
+                _ => return Err(SifError::LayerSize { len, width, height }),
+            }
+        }
+        match &self.candidates(len)[..] {
+            [] => Err(SifError::NoDimensions { len }),
+            [size] => Ok(*size),
+            candidates => Err(SifError::Ambiguous {
+                candidates: candidates.to_vec(),
+            }),
+        }
+    }
+}
+
+// An image in the Space Image Format: layers of digits, each `width` by
+// `height`, stacked front to back. How they combine depends on the palette,
+// the blend and which layers are visible.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sif {
+    width: usize,
+    height: usize,
+    layers: Vec<Vec<u8>>,
+    visible: Vec<bool>,
+    palette: LayerPalette,
+    blend: Blend,
+}
+
+impl Sif {
+    pub fn new(width: usize, height: usize, layers: Vec<Vec<u8>>) -> Result<Self, SifError> {
+        Sif::new_with_palette(width, height, layers, LayerPalette::default())
+    }
+
+    // Fails if any layer holds a value the palette doesn't know.
+    pub fn new_with_palette(
+        width: usize,
+        height: usize,
+        layers: Vec<Vec<u8>>,
+        palette: LayerPalette,
+    ) -> Result<Self, SifError> {
+        let size = width
+            .checked_mul(height)
+            .ok_or_else(|| SifError::LayerSize {
+                len: layers.iter().map(Vec::len).sum(),
+                width,
+                height,
+            })?;
+        if layers.is_empty() || size == 0 {
+            return Err(SifError::Empty);
+        }
+        if let Some(layer) = layers.iter().find(|layer| layer.len() != size) {
+            return Err(SifError::BitmapSize {
+                expected: size,
+                found: layer.len(),
+            });
+        }
+        Sif {
+            width,
+            height,
+            visible: vec![true; layers.len()],
+            layers,
+            palette: LayerPalette::default(),
+            blend: Blend::default(),
+        }
+        .with_palette(palette)
+    }
+
+    // Swaps the palette, failing if it doesn't know every value in the layers.
+    pub fn with_palette(mut self, palette: LayerPalette) -> Result<Self, SifError> {
+        for (layer, values) in self.layers.iter().enumerate() {
+            if let Some(position) = values.iter().position(|&value| !palette.contains(value)) {
+                return Err(SifError::NotInPalette {
+                    layer,
+                    position,
+                    value: values[position],
+                });
+            }
+        }
+        self.palette = palette;
+        Ok(self)
+    }
+
+    pub fn with_blend(mut self, blend: Blend) -> Self {
+        self.blend = blend;
+        self
+    }
+
+    // Hidden layers are left out of the composite, but still count for the
+    // histograms and checksum.
+    pub fn set_visible(&mut self, layer: usize, visible: bool) -> Result<(), SifError> {
+        match self.visible.get_mut(layer) {
+            Some(current) => {
+                *current = visible;
+                Ok(())
+            }
+            None => Err(SifError::NoLayer { layer }),
+        }
+    }
+
+    pub fn is_visible(&self, layer: usize) -> bool {
+        self.visible.get(layer).copied().unwrap_or(false)
+    }
+
+    pub fn palette(&self) -> &LayerPalette {
+        &self.palette
+    }
+
+    pub fn blend(&self) -> Blend {
+        self.blend
+    }
+
+    pub fn parse(input: &str, hint: Hint) -> Result<Self, SifError> {
+        let digits = input
+            .trim()
+            .chars()
+            .enumerate()
+            .map(|(position, found)| match found.to_digit(10) {
+                Some(digit) => Ok(digit as u8),
+                None => Err(SifError::InvalidDigit { position, found }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if digits.is_empty() {
+            return Err(SifError::Empty);
+        }
+        let (width, height) = hint.infer(digits.len())?;
+        Sif::new(
+            width,
+            height,
+            digits
+                .chunks(width * height)
+                .map(|layer| layer.to_vec())
+                .collect(),
+        )
+    }
+
+    // A single layer image, white wherever the bitmap is set.
+    pub fn from_bitmap(width: usize, height: usize, bitmap: &[bool]) -> Result<Self, SifError> {
+        let layer = bitmap
+            .iter()
+            .map(|&white| if white { WHITE } else { BLACK })
+            .collect();
+        Sif::new(width, height, vec![layer])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn layers(&self) -> &[Vec<u8>] {
+        &self.layers
+    }
+
+    pub fn encode(&self) -> Result<String, SifError> {
+        let mut encoded = String::new();
+        for (layer, values) in self.layers.iter().enumerate() {
+            for (position, &value) in values.iter().enumerate() {
+                if value > 9 {
+                    return Err(SifError::NotADigit {
+                        layer,
+                        position,
+                        value,
+                    });
+                }
+                encoded.push(char::from(b'0' + value));
+            }
+        }
+        Ok(encoded)
+    }
+
+    // How many times each value appears in each layer. Values that don't
+    // appear are left out.
+    pub fn histograms(&self) -> Vec<BTreeMap<u8, usize>> {
+        self.layers
+            .iter()
+            .map(|layer| {
+                let mut histogram = BTreeMap::new();
+                for &value in layer {
+                    *histogram.entry(value).or_insert(0) += 1;
+                }
+                histogram
+            })
+            .collect()
+    }
+
+    // The number of 1s times the number of 2s on the layer with fewest 0s,
+    // the first such layer if there's a tie.
+    pub fn checksum(&self) -> usize {
+        let count =
+            |histogram: &BTreeMap<u8, usize>, value| histogram.get(&value).copied().unwrap_or(0);
+        let histograms = self.histograms();
+        let layer = histograms
+            .iter()
+            .min_by_key(|histogram| count(histogram, BLACK))
+            .unwrap();
+        count(layer, WHITE) * count(layer, TRANSPARENT)
+    }
+
+    // Each pixel's visible values blended front to back, or None where
+    // they're all transparent.
+    pub fn composite(&self) -> Vec<Option<u8>> {
+        (0..self.width * self.height)
+            .map(|pixel| {
+                let values = self
+                    .layers
+                    .iter()
+                    .zip(&self.visible)
+                    .filter(|(_, &visible)| visible)
+                    .map(|(layer, _)| layer[pixel]);
+                self.blend.apply(&self.palette, values)
+            })
+            .collect()
+    }
+
+    // Plain PBM, where 1 is black. Anything that isn't white is drawn black.
+    pub fn to_pbm(&self) -> String {
+        let rows = self.rows(|value| if value == Some(WHITE) { 0 } else { 1 });
+        format!("P1\n{} {}\n{}", self.width, self.height, rows)
+    }
+
+    // Plain PGM using the palette's shades.
+    pub fn to_pgm(&self) -> String {
+        let rows = self.rows(|value| u32::from(self.palette.shade(value)));
+        format!("P2\n{} {}\n255\n{}", self.width, self.height, rows)
+    }
+
+    fn rows(&self, level: impl Fn(Option<u8>) -> u32) -> String {
+        self.composite()
+            .chunks(self.width)
+            .map(|row| {
+                let row: Vec<_> = row.iter().map(|&value| level(value).to_string()).collect();
+                format!("{}\n", row.join(" "))
+            })
+            .collect()
+    }
+
+    // Reads plain PBM (P1) or PGM (P2) into a single layer. In a PGM, 0 is
+    // black, the maximum is white and everything in between is transparent.
+    pub fn from_pnm(input: &str) -> Result<Self, SifError> {
+        let mut tokens = input
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .flat_map(str::split_whitespace);
+        let magic = tokens.next().ok_or_else(|| pnm("missing header"))?;
+        let mut number = |name: &str| {
+            tokens
+                .next()
+                .and_then(|token| token.parse::<usize>().ok())
+                .ok_or_else(|| pnm(&format!("invalid {}", name)))
+        };
+        let (width, height) = (number("width")?, number("height")?);
+        if width.checked_mul(height).is_none() {
+            return Err(pnm(&format!("{}x{} is too large", width, height)));
+        }
+        let layer = match magic {
+            // Bits don't have to be separated.
+            "P1" => tokens
+                .flat_map(str::chars)
+                .map(|bit| match bit {
+                    '0' => Ok(WHITE),
+                    '1' => Ok(BLACK),
+                    _ => Err(pnm(&format!("invalid bit {:?}", bit))),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            "P2" => {
+                let max = number("maximum")?;
+                tokens
+                    .map(|token| match token.parse::<usize>() {
+                        Ok(0) => Ok(BLACK),
+                        Ok(level) if level == max => Ok(WHITE),
+                        Ok(level) if level < max => Ok(TRANSPARENT),
+                        _ => Err(pnm(&format!("invalid level {:?}", token))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            _ => return Err(pnm(&format!("unsupported format {:?}", magic))),
+        };
+        Sif::new(width, height, vec![layer])
+    }
+}
+
+fn pnm(reason: &str) -> SifError {
+    SifError::Pnm {
+        reason: reason.to_string(),
+    }
+}
+
+// The composited image drawn with the palette's symbols. By default white is #
+// and black a space so any text stands out, with transparent pixels as dots.
+impl Display for Sif {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        for row in self.composite().chunks(self.width) {
+            let row: String = row
+                .iter()
+                .map(|&value| self.palette.symbol(value))
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let image = Sif::parse("123456789012\n", Hint::size(3, 2)).unwrap();
+        assert_eq!(
+            image.layers(),
+            &[vec![1, 2, 3, 4, 5, 6], vec![7, 8, 9, 0, 1, 2]][..]
+        );
+        assert_eq!(image.encode().unwrap(), "123456789012");
+        assert_eq!(
+            Sif::parse("12x4", Hint::size(2, 2)),
+            Err(SifError::InvalidDigit {
+                position: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            Sif::parse("12345", Hint::size(2, 2)),
+            Err(SifError::LayerSize {
+                len: 5,
+                width: 2,
+                height: 2
+            })
+        );
+        assert_eq!(Sif::parse(" \n", Hint::size(2, 2)), Err(SifError::Empty));
+    }
+
+    #[test]
+    fn test_infer() {
+        let hint = Hint {
+            width: Some(3),
+            layers: Some(2),
+            ..Hint::default()
+        };
+        assert_eq!(hint.infer(12), Ok((3, 2)));
+        assert_eq!(
+            Hint {
+                height: Some(2),
+                ..Hint::default()
+            }
+            .infer(12),
+            Err(SifError::Ambiguous {
+                candidates: vec![(1, 2), (2, 2), (3, 2), (6, 2)]
+            })
+        );
+        assert_eq!(
+            Hint {
+                layers: Some(5),
+                ..Hint::default()
+            }
+            .infer(12),
+            Err(SifError::NoDimensions { len: 12 })
+        );
+        assert_eq!(
+            Hint::size(usize::MAX, 2).infer(12),
+            Err(SifError::LayerSize {
+                len: 12,
+                width: usize::MAX,
+                height: 2
+            })
+        );
+        assert_eq!(
+            Hint {
+                layers: Some(usize::MAX),
+                ..Hint::default()
+            }
+            .infer(12),
+            Err(SifError::NoDimensions { len: 12 })
+        );
+        assert_eq!(
+            Hint::size(0, 3).infer(12),
+            Err(SifError::LayerSize {
+                len: 12,
+                width: 0,
+                height: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_checksum() {
+        let image = Sif::parse("001122012222", Hint::size(3, 2)).unwrap();
+        assert_eq!(
+            image.histograms(),
+            vec![
+                vec![(0, 2), (1, 2), (2, 2)].into_iter().collect(),
+                vec![(0, 1), (1, 1), (2, 4)]
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>()
+            ]
+        );
+        assert_eq!(image.checksum(), 4);
+    }
+
+    #[test]
+    fn test_composite() {
+        let image = Sif::parse("0222112222120000", Hint::size(2, 2)).unwrap();
+        assert_eq!(image.composite(), vec![Some(0), Some(1), Some(1), Some(0)]);
+        assert_eq!(image.to_string(), " #\n# \n");
+        let image = Sif::parse("2221", Hint::size(2, 1)).unwrap();
+        assert_eq!(image.composite(), vec![None, Some(1)]);
+        assert_eq!(image.to_string(), ".#\n");
+    }
+
+    #[test]
+    fn test_blend_and_visibility() {
+        let image = Sif::parse("0222112222120000", Hint::size(2, 2)).unwrap();
+        let last = image.clone().with_blend(Blend::LastOpaque);
+        assert_eq!(last.composite(), vec![Some(0); 4]);
+        let additive = image.clone().with_blend(Blend::Additive);
+        assert_eq!(
+            additive.composite(),
+            vec![Some(1), Some(1), Some(1), Some(0)]
+        );
+        let mut hidden = image.clone();
+        hidden.set_visible(0, false).unwrap();
+        assert!(!hidden.is_visible(0) && hidden.is_visible(1));
+        assert_eq!(hidden.composite(), vec![Some(1), Some(1), Some(1), Some(0)]);
+        for layer in &[1, 3] {
+            hidden.set_visible(*layer, false).unwrap();
+        }
+        assert_eq!(
+            hidden.set_visible(4, false),
+            Err(SifError::NoLayer { layer: 4 })
+        );
+        assert!(!hidden.is_visible(4));
+        assert_eq!(hidden.composite(), vec![None, None, Some(1), None]);
+        // Histograms still see every layer.
+        assert_eq!(hidden.checksum(), image.checksum());
+    }
+
+    #[test]
+    fn test_custom_palette() {
+        let palette = LayerPalette::new()
+            .with(1, '#', 255)
+            .with(2, 'o', 128)
+            .transparent(0);
+        let image = Sif::parse("0222112222120000", Hint::size(2, 2))
+            .unwrap()
+            .with_palette(palette)
+            .unwrap();
+        assert_eq!(image.composite(), vec![Some(1), Some(2), Some(2), Some(2)]);
+        assert_eq!(image.to_string(), "#o\noo\n");
+        assert_eq!(image.to_pgm(), "P2\n2 2\n255\n255 128\n128 128\n");
+        assert_eq!(
+            Sif::parse("0120", Hint::size(2, 2))
+                .unwrap()
+                .with_palette(LayerPalette::new().with(0, ' ', 0).transparent(2)),
+            Err(SifError::NotInPalette {
+                layer: 0,
+                position: 1,
+                value: 1
+            })
+        );
+        assert_eq!(
+            Sif::new(1, 1, vec![vec![12]]),
+            Err(SifError::NotInPalette {
+                layer: 0,
+                position: 0,
+                value: 12
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_values() {
+        let palette = LayerPalette::new()
+            .with(3, '-', 50)
+            .with(10, 'a', 100)
+            .with(200, 'b', 200)
+            .transparent(11);
+        let layers = vec![vec![11, 200, 3], vec![10, 11, 3]];
+        assert!(Sif::new(3, 1, layers.clone()).is_err());
+        let image = Sif::new_with_palette(3, 1, layers, palette).unwrap();
+        assert_eq!(image.composite(), vec![Some(10), Some(200), Some(3)]);
+        assert_eq!(image.to_string(), "ab-\n");
+        assert_eq!(
+            image.histograms()[0],
+            vec![(3, 1), (11, 1), (200, 1)].into_iter().collect()
+        );
+        assert_eq!(
+            image.encode(),
+            Err(SifError::NotADigit {
+                layer: 0,
+                position: 0,
+                value: 11
+            })
+        );
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let image = Sif::from_bitmap(2, 2, &[true, false, false, true]).unwrap();
+        assert_eq!(image.encode().unwrap(), "1001");
+        assert_eq!(
+            Sif::from_bitmap(2, 2, &[true]),
+            Err(SifError::BitmapSize {
+                expected: 4,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Sif::from_bitmap(usize::MAX, 2, &[true]),
+            Err(SifError::LayerSize {
+                len: 1,
+                width: usize::MAX,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_pnm() {
+        let image = Sif::parse("0222112222120000", Hint::size(2, 2)).unwrap();
+        let pbm = image.to_pbm();
+        assert_eq!(pbm, "P1\n2 2\n1 0\n0 1\n");
+        assert_eq!(Sif::from_pnm(&pbm).unwrap().composite(), image.composite());
+        let image = Sif::parse("2221", Hint::size(2, 1)).unwrap();
+        let pgm = image.to_pgm();
+        assert_eq!(pgm, "P2\n2 1\n255\n128 255\n");
+        assert_eq!(Sif::from_pnm(&pgm).unwrap(), image_of(2, 1, vec![2, 1]));
+        assert_eq!(
+            Sif::from_pnm("P1 # comment\n3 1\n011").unwrap(),
+            image_of(3, 1, vec![1, 0, 0])
+        );
+        assert_eq!(
+            Sif::from_pnm("P6\n1 1\n255\n").unwrap_err().to_string(),
+            "Invalid PBM/PGM: unsupported format \"P6\""
+        );
+        assert_eq!(
+            Sif::from_pnm("P1\n99999999999 99999999999\n1")
+                .unwrap_err()
+                .to_string(),
+            "Invalid PBM/PGM: 99999999999x99999999999 is too large"
+        );
+        assert_eq!(
+            Sif::from_pnm("P1\n2 1\n1"),
+            Err(SifError::BitmapSize {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    fn image_of(width: usize, height: usize, layer: Vec<u8>) -> Sif {
+        Sif::new(width, height, vec![layer]).unwrap()
+    }
+}
@@ -0,0 +1,61 @@
+use std::error::Error;
+
+use day_8_part_1::sif::{Hint, Sif, SifError, TRANSPARENT};
+use day_8_part_1::{HEIGHT, WIDTH};
+use grid::ocr::{read_frame, OcrError};
+use grid::{Frame, SparseGrid};
+use solution::{Answer, Solution};
+
+pub fn parse_image(input: &str) -> Result<Sif, SifError> {
+    Sif::parse(input, Hint::size(WIDTH, HEIGHT))
+}
+
+pub fn to_grid(image: &Sif) -> SparseGrid<char> {
+    image
+        .composite()
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            (
+                ((i % image.width()) as i64, (i / image.width()) as i64),
+                char::from(b'0' + value.unwrap_or(TRANSPARENT)),
+            )
+        })
+        .collect()
+}
+
+// The message spelled out by the white pixels.
+pub fn message(image: &Sif) -> Result<String, OcrError> {
+    read_frame(&Frame::from_grid(&to_grid(image)), '1')
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(Answer::from(message(&parse_image(input)?)?))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        8
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_message() {
+        let input = std::fs::read_to_string(solution::input_path(8)).unwrap();
+        assert_eq!(message(&parse_image(&input).unwrap()).unwrap(), "YEHEF");
+    }
+}
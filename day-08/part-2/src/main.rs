@@ -1,101 +1,34 @@
-use std::fmt;
 use std::fs::File;
 use std::io::prelude::*;
-use std::iter::FromIterator;
 
-#[derive(Debug)]
-struct Layer {
-    width: usize,
-    height: usize,
-    data: Vec<u32>,
-}
-
-impl Layer {
-    fn from_vec(input: Vec<u32>, width: usize, height: usize) -> Layer {
-        if input.len() != width * height {
-            panic!(
-                "Invalid layer format: {} != {}",
-                input.len(),
-                width * height
-            );
-        }
-        Layer {
-            width,
-            height,
-            data: input,
-        }
-    }
-}
-
-#[derive(Debug)]
-struct Image {
-    width: usize,
-    height: usize,
-    layers: Vec<Layer>,
-}
-
-impl Image {
-    fn from_vec(input: Vec<u32>, width: usize, height: usize) -> Image {
-        if input.len() % (width * height) != 0 {
-            panic!(
-                "Invalid file format: {} extra bytes",
-                input.len() % (width * height)
-            );
-        }
-        Image {
-            width,
-            height,
-            layers: input
-                .chunks(width * height)
-                .map(|vals| Layer::from_vec(vals.to_vec(), width, height))
-                .collect(),
-        }
-    }
-}
-
-impl fmt::Display for Image {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.layers[0]
-                .data
-                .iter()
-                .enumerate()
-                .map(|(i, _)| self.layers.iter().fold(2, |current, ref layer| {
-                    if current == 2 {
-                        layer.data[i]
-                    } else {
-                        current
-                    }
-                }))
-                .map(|x| std::char::from_digit(x, 10).unwrap())
-                .collect::<Vec<char>>()
-                .chunks(self.width)
-                .map(|x| {
-                    let mut vec = x.to_vec();
-                    vec.push('\n');
-                    String::from_iter(vec)
-                })
-                .collect::<Vec<String>>()
-                .into_iter()
-                .collect::<String>()
-        )
-    }
-}
+use day_8_part_2::{message, parse_image, to_grid};
+use grid::{Frame, Palette};
+use solution::input_path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut contents = String::new();
     {
-        let mut file = File::open("./input.txt")?;
+        let mut file = File::open(input_path(8))?;
         file.read_to_string(&mut contents)?;
     }
-    let numbers = contents
-        .trim()
-        .chars()
-        .map(|x| x.to_digit(10).unwrap())
-        .collect();
-    let image = Image::from_vec(numbers, 25, 6);
+    let image = parse_image(&contents)?;
     println!("{}", image);
+    println!("Message: {}", message(&image)?);
+    // Optionally save the decoded image, e.g. `cargo run -- message.png`, or
+    // as a plain PBM or PGM given one of those extensions.
+    if let Some(path) = std::env::args().nth(1) {
+        if path.ends_with(".pbm") {
+            std::fs::write(&path, image.to_pbm())?;
+        } else if path.ends_with(".pgm") {
+            std::fs::write(&path, image.to_pgm())?;
+        } else {
+            let palette = Palette::default()
+                .with('0', [0, 0, 0])
+                .with('1', [255, 255, 255])
+                .with('2', [128, 128, 128]);
+            grid::Image::from_frame(&Frame::from_grid(&to_grid(&image)), &palette, 10)
+                .save(&path)?;
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,33 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+use vm::lang::run_program;
+use vm::loader::parse_text;
+
+// Runs BOOST in test mode.
+const MODE: i64 = 1;
+
+// Anything other than a single output is a list of malfunctioning opcodes.
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let (outputs, _) = run_program(&parse_text(input)?, &[MODE]);
+    match outputs.as_slice() {
+        [keycode] => Ok(Answer::from(*keycode)),
+        _ => Err(format!("Malfunctioning opcodes: {:?}", outputs).into()),
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        9
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
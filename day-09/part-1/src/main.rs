@@ -1,8 +1,10 @@
+use solution::input_path;
 use vm::io::create_stdio_vmio;
 use vm::lang::load_from_file;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut vm = load_from_file("./input.txt")?;
+    let path = input_path(9);
+    let mut vm = load_from_file(&path)?;
     vm.io = create_stdio_vmio();
     vm.last().unwrap();
     Ok(())
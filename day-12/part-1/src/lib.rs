@@ -0,0 +1,270 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::iter::Sum;
+use std::ops::{Add, Sub};
+
+use solution::{Answer, Solution};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Vector3(i64, i64, i64);
+
+impl Add<Vector3> for Vector3 {
+    type Output = Vector3;
+
+    fn add(self, rhs: Vector3) -> Vector3 {
+        Vector3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl Sub<Vector3> for Vector3 {
+    type Output = Vector3;
+
+    fn sub(self, rhs: Vector3) -> Vector3 {
+        Vector3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl Vector3 {
+    fn energy(&self) -> i64 {
+        self.0.abs() + self.1.abs() + self.2.abs()
+    }
+}
+
+fn moon_energy(moon: &(Vector3, Vector3)) -> i64 {
+    moon.0.energy() * moon.1.energy()
+}
+
+struct NBodySim {
+    bodies: Vec<(Vector3, Vector3)>,
+}
+
+impl NBodySim {
+    fn energy(&self) -> i64 {
+        i64::sum(self.bodies.iter().map(moon_energy))
+    }
+}
+
+fn accelerate(pos: &Vector3, other_pos: &Vector3) -> Vector3 {
+    Vector3(
+        match pos.0.cmp(&other_pos.0) {
+            Ordering::Greater => -1,
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+        },
+        match pos.1.cmp(&other_pos.1) {
+            Ordering::Greater => -1,
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+        },
+        match pos.2.cmp(&other_pos.2) {
+            Ordering::Greater => -1,
+            Ordering::Less => 1,
+            Ordering::Equal => 0,
+        },
+    )
+}
+
+impl Iterator for NBodySim {
+    type Item = Vec<(Vector3, Vector3)>;
+
+    fn next(&mut self) -> Option<Vec<(Vector3, Vector3)>> {
+        self.bodies = self
+            .bodies
+            .iter()
+            .enumerate()
+            .map(|(index, (pos, vel))| {
+                let new_vel = self
+                    .bodies
+                    .iter()
+                    .enumerate()
+                    .filter(|(index2, _)| index != *index2)
+                    .map(|(_, (other_pos, _))| accelerate(pos, other_pos))
+                    .fold(*vel, |acc, val| acc + val);
+                (*pos + new_vel, new_vel)
+            })
+            .collect();
+        Some(self.bodies.clone())
+    }
+}
+
+// Each line is a moon's starting position, e.g. `<x=-1, y=0, z=2>`.
+fn parse_moons(input: &str) -> Result<NBodySim, Box<dyn Error>> {
+    let bodies = input
+        .lines()
+        .map(|line| {
+            let coords = line
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>')
+                .split(',')
+                .zip(&["x=", "y=", "z="])
+                .map(|(coord, prefix)| match coord.trim().strip_prefix(prefix) {
+                    Some(value) => Ok(value.parse::<i64>()?),
+                    None => Err(format!("Invalid moon: {}", line).into()),
+                })
+                .collect::<Result<Vec<i64>, Box<dyn Error>>>()?;
+            match coords.as_slice() {
+                &[x, y, z] => Ok((Vector3(x, y, z), Vector3(0, 0, 0))),
+                _ => Err(format!("Invalid moon: {}", line).into()),
+            }
+        })
+        .collect::<Result<_, Box<dyn Error>>>()?;
+    Ok(NBodySim { bodies })
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut simulation = parse_moons(input)?;
+
+    simulation.nth(999);
+
+    Ok(Answer::from(simulation.energy()))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_moons() {
+        let simulation = parse_moons("<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n").unwrap();
+        assert_eq!(
+            simulation.bodies,
+            vec!(
+                (Vector3(-1, 0, 2), Vector3(0, 0, 0)),
+                (Vector3(2, -10, -7), Vector3(0, 0, 0)),
+            )
+        );
+        assert!(parse_moons("<x=1, y=2>").is_err());
+        assert!(parse_moons("<x=1, q=2, z=3>").is_err());
+    }
+
+    #[test]
+    fn example_1() {
+        let mut simulation = NBodySim {
+            bodies: vec![
+                (Vector3(-1, 0, 2), Vector3(0, 0, 0)),
+                (Vector3(2, -10, -7), Vector3(0, 0, 0)),
+                (Vector3(4, -8, 8), Vector3(0, 0, 0)),
+                (Vector3(3, 5, -1), Vector3(0, 0, 0)),
+            ],
+        };
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(2, -1, 1), Vector3(3, -1, -1)),
+                (Vector3(3, -7, -4), Vector3(1, 3, 3)),
+                (Vector3(1, -7, 5), Vector3(-3, 1, -3)),
+                (Vector3(2, 2, 0), Vector3(-1, -3, 1)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(5, -3, -1), Vector3(3, -2, -2)),
+                (Vector3(1, -2, 2), Vector3(-2, 5, 6)),
+                (Vector3(1, -4, -1), Vector3(0, 3, -6)),
+                (Vector3(1, -4, 2), Vector3(-1, -6, 2)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(5, -6, -1), Vector3(0, -3, 0)),
+                (Vector3(0, 0, 6), Vector3(-1, 2, 4)),
+                (Vector3(2, 1, -5), Vector3(1, 5, -4)),
+                (Vector3(1, -8, 2), Vector3(0, -4, 0)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(2, -8, 0), Vector3(-3, -2, 1)),
+                (Vector3(2, 1, 7), Vector3(2, 1, 1)),
+                (Vector3(2, 3, -6), Vector3(0, 2, -1)),
+                (Vector3(2, -9, 1), Vector3(1, -1, -1)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(-1, -9, 2), Vector3(-3, -1, 2)),
+                (Vector3(4, 1, 5), Vector3(2, 0, -2)),
+                (Vector3(2, 2, -4), Vector3(0, -1, 2)),
+                (Vector3(3, -7, -1), Vector3(1, 2, -2)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(-1, -7, 3), Vector3(0, 2, 1)),
+                (Vector3(3, 0, 0), Vector3(-1, -1, -5)),
+                (Vector3(3, -2, 1), Vector3(1, -4, 5)),
+                (Vector3(3, -4, -2), Vector3(0, 3, -1)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(2, -2, 1), Vector3(3, 5, -2)),
+                (Vector3(1, -4, -4), Vector3(-2, -4, -4)),
+                (Vector3(3, -7, 5), Vector3(0, -5, 4)),
+                (Vector3(2, 0, 0), Vector3(-1, 4, 2)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(5, 2, -2), Vector3(3, 4, -3)),
+                (Vector3(2, -7, -5), Vector3(1, -3, -1)),
+                (Vector3(0, -9, 6), Vector3(-3, -2, 1)),
+                (Vector3(1, 1, 3), Vector3(-1, 1, 3)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(5, 3, -4), Vector3(0, 1, -2)),
+                (Vector3(2, -9, -3), Vector3(0, -2, 2)),
+                (Vector3(0, -8, 4), Vector3(0, 1, -2)),
+                (Vector3(1, 1, 5), Vector3(0, 0, 2)),
+            ))
+        );
+
+        assert_eq!(
+            simulation.next(),
+            Some(vec!(
+                (Vector3(2, 1, -3), Vector3(-3, -2, 1)),
+                (Vector3(1, -8, 0), Vector3(-1, 1, 3)),
+                (Vector3(3, -6, 1), Vector3(3, 2, -3)),
+                (Vector3(2, 0, 4), Vector3(1, -1, -1)),
+            ))
+        );
+
+        assert_eq!(simulation.energy(), 179);
+    }
+}
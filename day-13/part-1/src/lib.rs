@@ -0,0 +1,88 @@
+use std::error::Error;
+use vm::lang::load_from_str;
+
+use grid::{Palette, Render, SparseGrid};
+use solution::{Answer, Solution};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl From<i64> for Tile {
+    fn from(other: i64) -> Self {
+        match other {
+            0 => Tile::Empty,
+            1 => Tile::Wall,
+            2 => Tile::Block,
+            3 => Tile::Paddle,
+            4 => Tile::Ball,
+            _ => panic!(),
+        }
+    }
+}
+
+impl Render for Tile {
+    fn render(&self) -> char {
+        match self {
+            Tile::Empty => ' ',
+            Tile::Wall => '█',
+            Tile::Block => '░',
+            Tile::Paddle => '▔',
+            Tile::Ball => '•',
+        }
+    }
+}
+
+pub fn palette() -> Palette {
+    Palette::default()
+        .with('█', [128, 128, 128])
+        .with('░', [200, 120, 40])
+        .with('▔', [255, 255, 255])
+        .with('•', [255, 220, 0])
+}
+
+pub fn draw_tiles(input: &str) -> Result<SparseGrid<Tile>, Box<dyn Error>> {
+    let mut tiles: SparseGrid<Tile> = SparseGrid::new();
+    let mut vm = load_from_str(input)?;
+    while let Some(x) = vm.next() {
+        if let Some(y) = vm.next() {
+            if let Some(tile_id) = vm.next() {
+                tiles.insert((x, y), tile_id.into());
+            } else {
+                panic!()
+            }
+        } else {
+            panic!()
+        }
+    }
+    Ok(tiles)
+}
+
+pub fn count_blocks(tiles: &SparseGrid<Tile>) -> usize {
+    tiles.values().filter(|&&x| x == Tile::Block).count()
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    Ok(Answer::from(count_blocks(&draw_tiles(input)?)))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
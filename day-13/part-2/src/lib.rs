@@ -0,0 +1,115 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::rc::Rc;
+use std::sync::Mutex;
+use vm::program::Program;
+use vm::IntcodeVM;
+
+use day_13_part_1::Tile;
+use grid::SparseGrid;
+use solution::{Answer, Solution};
+
+#[derive(Clone)]
+struct Input {
+    positions: Rc<Mutex<(i64, i64)>>,
+}
+
+impl Iterator for Input {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        let lock = self.positions.lock().unwrap();
+        Some(match lock.0.cmp(&lock.1) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        })
+    }
+}
+
+impl Input {
+    fn set_ball_pos(&mut self, ball: i64) {
+        let mut lock = self.positions.lock().unwrap();
+        *lock = (ball, lock.1);
+    }
+
+    fn set_paddle_pos(&mut self, paddle: i64) {
+        let mut lock = self.positions.lock().unwrap();
+        *lock = (lock.0, paddle);
+    }
+}
+
+pub enum Update {
+    Tile(Tile),
+    Score,
+}
+
+// Plays the game with the paddle following the ball, yielding each change to
+// the screen.
+pub struct Game {
+    vm: IntcodeVM<'static, i64>,
+    input: Input,
+    pub tiles: SparseGrid<Tile>,
+    pub score: i64,
+}
+
+impl Game {
+    pub fn new(input: &str) -> Result<Game, Box<dyn Error>> {
+        let mut vm = Program::parse(input)?.variant().free_play().instantiate();
+        let input = Input {
+            positions: Rc::new(Mutex::new((0, 0))),
+        };
+        vm.io.input = Some(Box::new(input.clone()));
+        Ok(Game {
+            vm,
+            input,
+            tiles: SparseGrid::new(),
+            score: 0,
+        })
+    }
+}
+
+impl Iterator for Game {
+    type Item = Update;
+
+    fn next(&mut self) -> Option<Update> {
+        let x = self.vm.next()?;
+        let y = self.vm.next().expect("No y coordinate returned");
+        let tile_id_or_score = self.vm.next().expect("No tile returned");
+        if x == -1 {
+            self.score = tile_id_or_score;
+            return Some(Update::Score);
+        }
+        let tile = tile_id_or_score.into();
+        self.tiles.insert((x, y), tile);
+        if tile == Tile::Paddle {
+            self.input.set_paddle_pos(x)
+        }
+        if tile == Tile::Ball {
+            self.input.set_ball_pos(x);
+        }
+        Some(Update::Tile(tile))
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut game = Game::new(input)?;
+    for _ in game.by_ref() {}
+    Ok(Answer::from(game.score))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        13
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
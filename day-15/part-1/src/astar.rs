@@ -1,3 +1,4 @@
+use grid::Frame;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
@@ -11,7 +12,7 @@ fn dist(a: Position, b: Position) -> i64 {
 }
 
 pub struct AStar {
-    pub dist_fn: &'static dyn (Fn(Position, Position) -> i64),
+    pub dist_fn: &'static dyn Fn(Position, Position) -> i64,
     pub open_set: HashMap<Position, (i64, Option<Position>)>,
     pub closed_set: HashMap<Position, (i64, Option<Position>)>,
     pub maze: Maze,
@@ -34,6 +35,17 @@ impl AStar {
     }
 }
 
+impl AStar {
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.maze.map);
+        for &pos in self.open_set.keys() {
+            frame.set(pos, 'X');
+        }
+        frame.set(self.maze.start, 'S');
+        frame
+    }
+}
+
 impl Iterator for AStar {
     type Item = ();
 
@@ -53,7 +65,7 @@ impl Iterator for AStar {
 
             for dir in Dir::all() {
                 let new_pos = dir.move_in_dir(pos);
-                if self.maze.map[pos.1 as usize][pos.0 as usize] == Tile::Wall {
+                if self.maze.map.get(&pos) == Some(&Tile::Wall) {
                     continue;
                 }
                 if self.closed_set.contains_key(&new_pos) {
@@ -78,15 +90,8 @@ impl From<Maze> for AStar {
         let target = other
             .map
             .iter()
-            .enumerate()
-            .filter_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(x, tile)| ((x as i64, y as i64), *tile))
-                    .find(|(_, tile)| *tile == Tile::Oxygen)
-            })
-            .map(|(pos, _)| pos)
-            .next()
+            .find(|(_, tile)| **tile == Tile::Oxygen)
+            .map(|(&pos, _)| pos)
             .unwrap();
         AStar {
             dist_fn: &dist,
@@ -100,29 +105,38 @@ impl From<Maze> for AStar {
 
 impl Display for AStar {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(
-            f,
-            "{}",
-            self.maze
-                .map
-                .iter()
-                .enumerate()
-                .map(|(y, row)| {
-                    row.iter()
-                        .enumerate()
-                        .map(|(x, tile)| {
-                            if (x as i64, y as i64) == self.maze.start {
-                                return "S";
-                            }
-                            if self.open_set.contains_key(&(x as i64, y as i64)) {
-                                return "X";
-                            }
-                            tile.into()
-                        })
-                        .collect::<String>()
-                        + "\n"
+        write!(f, "{}", self.frame())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use grid::{Backend, BufferBackend, SparseGrid};
+
+    #[test]
+    fn test_search_frames() {
+        let map: SparseGrid<Tile> = "#####\n#..O#\n#####"
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars().enumerate().map(move |(x, tile)| {
+                    let tile = match tile {
+                        '#' => Tile::Wall,
+                        'O' => Tile::Oxygen,
+                        _ => Tile::Empty,
+                    };
+                    ((x as i64 - 1, y as i64 - 1), tile)
                 })
-                .collect::<String>()
-        )
+            })
+            .collect();
+        let mut astar: AStar = Maze { map, start: (0, 0) }.into();
+        let mut backend = BufferBackend::new();
+        while let Some(()) = astar.next() {
+            backend.draw(&astar.frame()).unwrap();
+        }
+        assert_eq!(backend.frames[0].rows(), vec!["#X###", "XSXO#", "#X###"]);
+        assert_eq!(backend.last().unwrap().rows()[1], "XS·X#");
+        assert_eq!(astar.get_path_to(astar.target).unwrap().len(), 3);
     }
 }
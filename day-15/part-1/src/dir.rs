@@ -33,9 +33,9 @@ impl Dir {
     }
 }
 
-impl Into<i64> for Dir {
-    fn into(self) -> i64 {
-        match self {
+impl From<Dir> for i64 {
+    fn from(val: Dir) -> Self {
+        match val {
             Dir::Up => 1,
             Dir::Left => 3,
             Dir::Down => 2,
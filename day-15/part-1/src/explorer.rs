@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use grid::{Frame, SparseGrid};
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 use std::sync::Mutex;
-use vm::lang::load_from_file;
+use vm::lang::load_from_str;
 
 use super::dir::Dir;
 use super::maze::Maze;
@@ -11,7 +11,7 @@ use super::Position;
 
 #[derive(Clone)]
 pub struct InstructionIterator {
-    pub map: Rc<Mutex<HashMap<Position, Tile>>>,
+    pub map: Rc<Mutex<SparseGrid<Tile>>>,
     pub history_stack: Rc<Mutex<Vec<Position>>>,
 }
 
@@ -46,7 +46,7 @@ impl Iterator for InstructionIterator {
         let mut location_stack = self.history_stack.lock().unwrap();
         for dir in Dir::all() {
             let location = dir.move_in_dir(current_position);
-            if !seen_locations.contains_key(&location) {
+            if !seen_locations.contains(&location) {
                 location_stack.push(location);
                 return Some(dir.into());
             }
@@ -65,48 +65,31 @@ impl Iterator for InstructionIterator {
     }
 }
 
+impl InstructionIterator {
+    pub fn frame(&self) -> Frame {
+        let mut frame = Frame::from_grid(&self.map.lock().unwrap());
+        for &position in self.history_stack.lock().unwrap().iter() {
+            frame.set(position, 'P');
+        }
+        if let Some(position) = self.current_position() {
+            frame.set(position, 'D');
+        }
+        frame
+    }
+}
+
 impl Display for InstructionIterator {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        let current_position = self.current_position().unwrap();
-        let location_stack = self.history_stack.lock().unwrap();
-        let seen_locations = self.map.lock().unwrap();
-
-        let min_x = *seen_locations.keys().map(|(x, _)| x).min().unwrap();
-        let min_y = *seen_locations.keys().map(|(_, y)| y).min().unwrap();
-        let max_x = *seen_locations.keys().map(|(x, _)| x).max().unwrap();
-        let max_y = *seen_locations.keys().map(|(_, y)| y).max().unwrap();
-        write!(
-            f,
-            "{}",
-            (min_y..=max_y)
-                .map(|y| {
-                    (min_x..=max_x)
-                        .map(|x| {
-                            if current_position == (x, y) {
-                                return "D";
-                            }
-                            if location_stack.contains(&(x, y)) {
-                                return "P";
-                            }
-                            seen_locations
-                                .get(&(x, y))
-                                .unwrap_or(&Tile::Unexplored)
-                                .into()
-                        })
-                        .collect::<String>()
-                        + "\n"
-                })
-                .collect::<String>()
-        )
+        write!(f, "{}", self.frame())
     }
 }
 
-pub fn explore() -> Result<Maze, Box<dyn std::error::Error>> {
+pub fn explore(input: &str) -> Result<Maze, Box<dyn std::error::Error>> {
     let mut iterator = InstructionIterator {
-        map: Rc::new(Mutex::new(HashMap::new())),
+        map: Rc::new(Mutex::new(SparseGrid::new())),
         history_stack: Rc::new(Mutex::new(vec![(0, 0)])),
     };
-    let mut vm = load_from_file("./input.txt")?;
+    let mut vm = load_from_str(input)?;
     vm.io.input = Some(Box::new(iterator.clone()));
     while iterator.should_continue() {
         iterator.handle_output(vm.next().unwrap().into());
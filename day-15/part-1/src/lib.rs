@@ -1,7 +1,40 @@
+use std::error::Error;
+
+use solution::{Answer, Solution};
+
 pub mod astar;
 pub mod dir;
 pub mod explorer;
 pub mod maze;
 pub mod tile;
 
+use self::astar::AStar;
+use self::explorer::explore;
+
 pub type Position = (i64, i64);
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut astar: AStar = explore(input)?.into();
+    for _ in astar.by_ref() {}
+    let (path_len, _) = astar
+        .closed_set
+        .get(&astar.target)
+        .ok_or("No path to the oxygen system")?;
+    Ok(Answer::from(*path_len))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
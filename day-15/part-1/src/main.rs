@@ -1,39 +1,42 @@
-use std::io::{stdin, stdout, Write};
-use termion::screen::AlternateScreen;
+use grid::{Animation, Backend, TermionBackend};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::stdin;
 
-mod astar;
-mod dir;
-mod explorer;
-mod maze;
-mod tile;
-
-use self::astar::AStar;
-use self::explorer::explore;
-use self::maze::Maze;
-
-type Position = (i64, i64);
+use day15part1::astar::AStar;
+use day15part1::explorer::explore;
+use day15part1::maze::Maze;
+use day15part1::tile::palette;
+use solution::input_path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut screen = AlternateScreen::from(stdout());
-    let maze: Maze = explore()?;
+    let mut contents = String::new();
+    {
+        let mut file = File::open(input_path(15))?;
+        file.read_to_string(&mut contents)?;
+    }
+    let mut backend = TermionBackend::new();
+    // Optionally record the search, e.g. `cargo run -- search.gif`
+    let path = std::env::args().nth(1);
+    let mut animation = Animation::new(palette(), 4, 2);
+    let maze: Maze = explore(&contents)?;
     println!("{}", maze);
     let mut astar: AStar = maze.into();
     while let Some(()) = astar.next() {
-        write!(
-            screen,
-            "{}{}{}{}",
-            termion::cursor::Goto(1, 1),
-            termion::clear::All,
-            astar,
-            termion::cursor::Hide
-        )?;
+        let frame = astar.frame();
+        backend.draw(&frame)?;
+        if path.is_some() {
+            animation.draw(&frame)?;
+        }
+    }
+    if let Some(path) = path {
+        animation.save(&path)?;
     }
     println!(
         "Path len: {}",
         astar.get_path_to(astar.target).unwrap().len()
     );
     println!("Path: {:?}", astar.get_path_to(astar.target).unwrap());
-    screen.flush()?;
     stdin().read_line(&mut String::new())?;
     Ok(())
 }
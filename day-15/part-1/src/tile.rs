@@ -1,9 +1,10 @@
+use grid::{Palette, Render};
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Tile {
     Empty,
     Wall,
     Oxygen,
-    Unexplored,
 }
 
 impl From<i64> for Tile {
@@ -17,13 +18,23 @@ impl From<i64> for Tile {
     }
 }
 
-impl Into<&str> for &Tile {
-    fn into(self) -> &'static str {
+impl Render for Tile {
+    fn render(&self) -> char {
         match self {
-            Tile::Empty => "·",
-            Tile::Wall => "#",
-            Tile::Oxygen => "O",
-            Tile::Unexplored => " ",
+            Tile::Empty => '·',
+            Tile::Wall => '#',
+            Tile::Oxygen => 'O',
         }
     }
 }
+
+// Colours for saving frames of the search, including the overlays drawn by
+// `AStar::frame`.
+pub fn palette() -> Palette {
+    Palette::default()
+        .with('#', [128, 128, 128])
+        .with('·', [40, 40, 40])
+        .with('O', [60, 120, 255])
+        .with('S', [0, 200, 0])
+        .with('X', [220, 40, 40])
+}
@@ -0,0 +1,54 @@
+use std::error::Error;
+
+use day15part1::astar::AStar;
+use day15part1::explorer::explore;
+use day15part1::maze::Maze;
+use solution::{Answer, Solution};
+
+type Position = (i64, i64);
+
+fn zero(_: Position, _: Position) -> i64 {
+    0
+}
+
+// Searches outwards from the oxygen system with no target, so every open tile
+// ends up in the closed set with its distance from the oxygen.
+pub fn flood(maze: Maze) -> AStar {
+    let mut astar: AStar = maze.into();
+    astar.open_set.clear();
+    astar.open_set.insert(astar.target, (0, None));
+    astar.target = (500, 500);
+    astar.dist_fn = &zero;
+    astar
+}
+
+pub fn furthest(astar: &AStar) -> Option<(Position, i64)> {
+    astar
+        .closed_set
+        .iter()
+        .max_by_key(|(_, (path_len, _))| *path_len)
+        .map(|(&pos, &(path_len, _))| (pos, path_len))
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let mut astar = flood(explore(input)?);
+    for _ in astar.by_ref() {}
+    let (_, len) = furthest(&astar).ok_or("Nothing was explored")?;
+    Ok(Answer::from(len))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        15
+    }
+
+    fn part(&self) -> u32 {
+        2
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
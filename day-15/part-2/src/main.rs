@@ -1,43 +1,40 @@
-use std::io::{stdin, stdout, Write};
-use termion::screen::AlternateScreen;
+use grid::{Animation, Backend, TermionBackend};
+use std::fs::File;
+use std::io::prelude::*;
+use std::io::stdin;
 
-use day15part1::astar::AStar;
 use day15part1::explorer::explore;
 use day15part1::maze::Maze;
-
-type Position = (i64, i64);
-
-fn zero(_: Position, _: Position) -> i64 {
-    0
-}
+use day15part1::tile::palette;
+use day_15_part_2::{flood, furthest};
+use solution::input_path;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut screen = AlternateScreen::from(stdout());
-    let maze: Maze = explore()?;
+    let mut contents = String::new();
+    {
+        let mut file = File::open(input_path(15))?;
+        file.read_to_string(&mut contents)?;
+    }
+    let mut backend = TermionBackend::new();
+    // Optionally record the search, e.g. `cargo run -- search.gif`
+    let path = std::env::args().nth(1);
+    let mut animation = Animation::new(palette(), 4, 2);
+    let maze: Maze = explore(&contents)?;
     println!("{}", maze);
-    let mut astar: AStar = maze.into();
-    astar.open_set.clear();
-    astar.open_set.insert(astar.target, (0, None));
-    astar.target = (500, 500);
-    astar.dist_fn = &zero;
+    let mut astar = flood(maze);
     while let Some(()) = astar.next() {
-        write!(
-            screen,
-            "{}{}{}{}",
-            termion::cursor::Goto(1, 1),
-            termion::clear::All,
-            astar,
-            termion::cursor::Hide
-        )?;
+        let frame = astar.frame();
+        backend.draw(&frame)?;
+        if path.is_some() {
+            animation.draw(&frame)?;
+        }
+    }
+    if let Some(path) = path {
+        animation.save(&path)?;
     }
-    let (pos, (len, _)) = astar
-        .closed_set
-        .iter()
-        .max_by_key(|(_, (path_len, _))| *path_len)
-        .unwrap();
+    let (pos, len) = furthest(&astar).unwrap();
     println!("Max len: {}", len);
-    println!("Path: {:?}", astar.get_path_to(*pos).unwrap());
-    screen.flush()?;
+    println!("Path: {:?}", astar.get_path_to(pos).unwrap());
     stdin().read_line(&mut String::new())?;
     Ok(())
 }
@@ -1,7 +1,10 @@
-use std::convert::TryFrom;
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
 use std::ops::Range;
 
 use rayon::prelude::*;
+use solution::{Answer, Solution};
 
 struct FFTStepPart<'a> {
     slice: &'a [i64],
@@ -116,6 +119,16 @@ pub enum ParseError {
     InvalidDigit(char),
 }
 
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            ParseError::InvalidDigit(c) => write!(f, "Invalid digit: {:?}", c),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
 impl TryFrom<(&str, usize)> for FFTPhases {
     type Error = ParseError;
 
@@ -123,7 +136,7 @@ impl TryFrom<(&str, usize)> for FFTPhases {
         Ok((
             string
                 .chars()
-                .map(|c| c.to_digit(10).ok_or_else(|| ParseError::InvalidDigit(c)))
+                .map(|c| c.to_digit(10).ok_or(ParseError::InvalidDigit(c)))
                 .collect::<Result<Vec<u32>, _>>()?,
             offset,
         )
@@ -140,10 +153,41 @@ impl Iterator for FFTPhases {
     }
 }
 
+// The first eight digits after a hundred phases.
+pub fn message(mut phases: FFTPhases) -> String {
+    phases
+        .nth(99)
+        .unwrap()
+        .into_iter()
+        .take(8)
+        .map(|digit| digit.to_string())
+        .collect()
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let phases: FFTPhases = (input.trim(), 0).try_into()?;
+    Ok(Answer::from(message(phases)))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        16
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use std::convert::TryInto;
 
     #[test]
     fn test_get_range_0offset_1multiple() {
@@ -0,0 +1,127 @@
+use std::error::Error;
+use vm::lang::load_from_str;
+
+use day15part1::dir::Dir;
+use day15part1::Position;
+use solution::{Answer, Solution};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Empty,
+    Scaffolding,
+    BotUp,
+}
+
+impl From<char> for Tile {
+    fn from(other: char) -> Self {
+        match other {
+            '.' => Tile::Empty,
+            '#' => Tile::Scaffolding,
+            '^' => Tile::BotUp,
+            _ => panic!(),
+        }
+    }
+}
+
+impl From<&Tile> for &str {
+    fn from(val: &Tile) -> Self {
+        match val {
+            Tile::Empty => ".",
+            Tile::Scaffolding => "#",
+            Tile::BotUp => "^",
+        }
+    }
+}
+
+struct Map {
+    grid: Vec<Vec<Tile>>,
+}
+
+impl Map {
+    fn alignment_parameters(&self) -> Vec<i64> {
+        (1..(self.grid.len() as i64 - 1))
+            .flat_map(|y| {
+                (1..(self.grid[0].len() as i64 - 1))
+                    .filter_map(|x| {
+                        let pos = (x, y);
+                        if self.tile_at(&pos) != Tile::Scaffolding {
+                            return None;
+                        }
+                        for dir in Dir::all() {
+                            if self.tile_at(&dir.move_in_dir(pos)) != Tile::Scaffolding {
+                                return None;
+                            }
+                        }
+                        Some(x * y)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn tile_at(&self, pos: &Position) -> Tile {
+        self.grid[pos.1 as usize][pos.0 as usize]
+    }
+}
+
+impl From<&str> for Map {
+    fn from(other: &str) -> Map {
+        Map {
+            grid: other
+                .trim()
+                .split('\n')
+                .map(|line| line.chars().map(|x| x.into()).collect())
+                .collect(),
+        }
+    }
+}
+
+pub fn solve(input: &str) -> Result<Answer, Box<dyn Error>> {
+    let vm = load_from_str(input)?;
+    let map: Map = vm
+        .map(|x| (x as u8) as char)
+        .collect::<String>()
+        .as_str()
+        .into();
+    Ok(Answer::from(map.alignment_parameters().iter().sum::<i64>()))
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    fn day(&self) -> u32 {
+        17
+    }
+
+    fn part(&self) -> u32 {
+        1
+    }
+
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>> {
+        solve(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_alignment_parameters() {
+        assert_eq!(
+            Map::from(
+                "
+..#..........
+..#..........
+#######...###
+#.#...#...#.#
+#############
+..#...#...#..
+..#####...^..
+            "
+            )
+            .alignment_parameters(),
+            vec![4, 8, 24, 40]
+        )
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+
+use super::image::{Image, Palette, Rgb};
+use super::{gif, Backend, Bounds, Frame};
+
+// Records every frame drawn so they can be written out as an animated GIF.
+// Frames that cover different areas are lined up by their grid positions on a
+// canvas big enough for all of them.
+pub struct Animation {
+    pub palette: Palette,
+    pub scale: usize,
+    // Hundredths of a second between frames.
+    pub delay: u16,
+    frames: Vec<Frame>,
+}
+
+impl Animation {
+    pub fn new(palette: Palette, scale: usize, delay: u16) -> Animation {
+        Animation {
+            palette,
+            scale,
+            delay,
+            frames: vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    fn canvas_bounds(&self) -> Option<Bounds> {
+        self.frames.iter().filter_map(|frame| frame.bounds()).fold(
+            None,
+            |canvas: Option<Bounds>, bounds| {
+                Some(match canvas {
+                    Some(mut canvas) => {
+                        canvas.include((bounds.min_x, bounds.min_y));
+                        canvas.include((bounds.max_x, bounds.max_y));
+                        canvas
+                    }
+                    None => bounds,
+                })
+            },
+        )
+    }
+
+    pub fn images(&self) -> Vec<Image> {
+        let canvas_bounds = match self.canvas_bounds() {
+            Some(bounds) => bounds,
+            None => return vec![],
+        };
+        let blank = Image::new(
+            canvas_bounds.width() * self.scale,
+            canvas_bounds.height() * self.scale,
+            self.palette.background,
+        );
+        self.frames
+            .iter()
+            .map(|frame| {
+                let mut canvas = blank.clone();
+                if let Some(bounds) = frame.bounds() {
+                    canvas.draw(
+                        &Image::from_frame(frame, &self.palette, self.scale),
+                        (bounds.min_x - canvas_bounds.min_x) as usize * self.scale,
+                        (bounds.min_y - canvas_bounds.min_y) as usize * self.scale,
+                    );
+                }
+                canvas
+            })
+            .collect()
+    }
+
+    pub fn to_gif(&self) -> std::io::Result<Vec<u8>> {
+        let images = self.images();
+        let (width, height) = images
+            .first()
+            .map_or((0, 0), |image| (image.width, image.height));
+        if width > usize::from(u16::MAX) || height > usize::from(u16::MAX) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "Animation is too large for a GIF",
+            ));
+        }
+        let mut colours: Vec<Rgb> = vec![];
+        let mut indices: HashMap<Rgb, u8> = HashMap::new();
+        let mut frames = vec![];
+        for image in &images {
+            let mut frame = Vec::with_capacity(image.pixels().len());
+            for pixel in image.pixels() {
+                let index = match indices.get(pixel) {
+                    Some(&index) => index,
+                    None if colours.len() < 256 => {
+                        colours.push(*pixel);
+                        indices.insert(*pixel, (colours.len() - 1) as u8);
+                        (colours.len() - 1) as u8
+                    }
+                    None => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            "Animation uses more than 256 colours",
+                        ))
+                    }
+                };
+                frame.push(index);
+            }
+            frames.push(frame);
+        }
+        Ok(gif::encode(width, height, &colours, &frames, self.delay))
+    }
+
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        File::create(path)?.write_all(&self.to_gif()?)
+    }
+}
+
+impl Backend for Animation {
+    fn draw(&mut self, frame: &Frame) -> std::io::Result<()> {
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SparseGrid;
+
+    #[test]
+    fn test_frames_share_a_canvas() {
+        let mut animation = Animation::new(Palette::default(), 1, 10);
+        let mut grid = SparseGrid::new();
+        grid.insert((0, 0), '#');
+        animation.draw(&Frame::from_grid(&grid)).unwrap();
+        grid.insert((-1, 1), '#');
+        animation.draw(&Frame::from_grid(&grid)).unwrap();
+
+        let images = animation.images();
+        assert_eq!(images.len(), 2);
+        assert_eq!((images[0].width, images[0].height), (2, 2));
+        assert_eq!(
+            images[0].pixels(),
+            &[[0, 0, 0], [255, 255, 255], [0, 0, 0], [0, 0, 0]]
+        );
+        assert_eq!(
+            images[1].pixels(),
+            &[[0, 0, 0], [255, 255, 255], [255, 255, 255], [0, 0, 0]]
+        );
+
+        let gif = animation.to_gif().unwrap();
+        assert_eq!(gif[..6], *b"GIF89a");
+        assert_eq!(gif[gif.len() - 1], 0x3b);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut animation = Animation::new(Palette::default(), 1, 10);
+        assert_eq!(animation.to_gif().unwrap()[6..10], [0, 0, 0, 0]);
+        animation.draw(&Frame::new(None, ' ')).unwrap();
+        assert!(animation.images().is_empty());
+        let gif = gif::encode(0, 0, &[], &[vec![]], 10);
+        assert_eq!(gif[6..10], [0, 0, 0, 0]);
+        assert_eq!(gif[gif.len() - 1], 0x3b);
+    }
+
+    #[test]
+    fn test_too_many_colours() {
+        let mut palette = Palette::default();
+        let mut grid = SparseGrid::new();
+        for value in 0..300 {
+            let character = std::char::from_u32(0x100 + value).unwrap();
+            palette = palette.with(character, [value as u8, (value >> 8) as u8, 0]);
+            grid.insert((i64::from(value), 0), character);
+        }
+        let mut animation = Animation::new(palette, 1, 10);
+        animation.draw(&Frame::from_grid(&grid)).unwrap();
+        assert_eq!(
+            animation.to_gif().unwrap_err().kind(),
+            ErrorKind::InvalidData
+        );
+    }
+}
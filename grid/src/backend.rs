@@ -0,0 +1,187 @@
+use std::io::{stdout, Stdout, Write};
+use termion::screen::AlternateScreen;
+
+use super::Frame;
+
+pub trait Backend {
+    fn draw(&mut self, frame: &Frame) -> std::io::Result<()>;
+}
+
+// Writes every frame out in full as plain text.
+pub struct TextBackend<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> TextBackend<W> {
+    pub fn new(writer: W) -> TextBackend<W> {
+        TextBackend { writer }
+    }
+}
+
+impl<W: Write> Backend for TextBackend<W> {
+    fn draw(&mut self, frame: &Frame) -> std::io::Result<()> {
+        write!(self.writer, "{}", frame)?;
+        self.writer.flush()
+    }
+}
+
+// Keeps every frame drawn, for tests.
+#[derive(Default)]
+pub struct BufferBackend {
+    pub frames: Vec<Frame>,
+}
+
+impl BufferBackend {
+    pub fn new() -> BufferBackend {
+        BufferBackend::default()
+    }
+
+    pub fn last(&self) -> Option<&Frame> {
+        self.frames.last()
+    }
+}
+
+impl Backend for BufferBackend {
+    fn draw(&mut self, frame: &Frame) -> std::io::Result<()> {
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+}
+
+// Live view on the alternate screen. Only the cells that changed since the
+// last frame are redrawn, unless the frame has changed shape.
+pub struct TermionBackend<W: Write> {
+    screen: AlternateScreen<W>,
+    previous: Option<Frame>,
+}
+
+impl TermionBackend<Stdout> {
+    pub fn new() -> TermionBackend<Stdout> {
+        TermionBackend::default()
+    }
+}
+
+impl Default for TermionBackend<Stdout> {
+    fn default() -> Self {
+        TermionBackend::with_writer(stdout())
+    }
+}
+
+impl<W: Write> TermionBackend<W> {
+    pub fn with_writer(writer: W) -> TermionBackend<W> {
+        TermionBackend {
+            screen: AlternateScreen::from(writer),
+            previous: None,
+        }
+    }
+}
+
+impl<W: Write> Backend for TermionBackend<W> {
+    fn draw(&mut self, frame: &Frame) -> std::io::Result<()> {
+        let previous = self.previous.take().filter(|previous| {
+            previous.bounds() == frame.bounds()
+                && previous.caption.is_some() == frame.caption.is_some()
+        });
+        if previous.is_none() {
+            write!(self.screen, "{}", termion::clear::All)?;
+        }
+        if let Some(caption) = &frame.caption {
+            if previous
+                .as_ref()
+                .and_then(|previous| previous.caption.as_ref())
+                != Some(caption)
+            {
+                write!(
+                    self.screen,
+                    "{}{}{}",
+                    termion::cursor::Goto(1, 1),
+                    termion::clear::CurrentLine,
+                    caption
+                )?;
+            }
+        }
+        let top = if frame.caption.is_some() { 2 } else { 1 };
+        let previous_rows = previous.map(|previous| previous.rows());
+        for (y, row) in frame.rows().iter().enumerate() {
+            let previous_row = previous_rows
+                .as_ref()
+                .map(|rows| rows[y].chars().collect::<Vec<char>>());
+            for (x, value) in row.chars().enumerate() {
+                if previous_row.as_ref().map(|row| row[x]) != Some(value) {
+                    write!(
+                        self.screen,
+                        "{}{}",
+                        termion::cursor::Goto(x as u16 + 1, y as u16 + top),
+                        value
+                    )?;
+                }
+            }
+        }
+        write!(self.screen, "{}", termion::cursor::Hide)?;
+        self.screen.flush()?;
+        self.previous = Some(frame.clone());
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for TermionBackend<W> {
+    fn drop(&mut self) {
+        write!(self.screen, "{}", termion::cursor::Show).ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SparseGrid;
+
+    fn frame(cells: &[((i64, i64), char)]) -> Frame {
+        let grid: SparseGrid<char> = cells.iter().copied().collect();
+        Frame::from_grid(&grid)
+    }
+
+    #[test]
+    fn test_text_backend() {
+        let mut output = vec![];
+        TextBackend::new(&mut output)
+            .draw(&frame(&[((0, 0), 'a'), ((1, 1), 'b')]).with_caption("Score: 1"))
+            .unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Score: 1\na \n b\n");
+    }
+
+    #[test]
+    fn test_buffer_backend() {
+        let mut backend = BufferBackend::new();
+        backend.draw(&frame(&[((0, 0), 'a')])).unwrap();
+        backend.draw(&frame(&[((0, 0), 'b')])).unwrap();
+        assert_eq!(backend.frames.len(), 2);
+        assert_eq!(backend.last().unwrap().rows(), vec!["b"]);
+    }
+
+    #[test]
+    fn test_termion_backend_only_redraws_changes() {
+        let mut backend = TermionBackend::with_writer(vec![]);
+        backend
+            .draw(&frame(&[((0, 0), 'a'), ((1, 0), 'b')]))
+            .unwrap();
+        let first = backend.screen.len();
+        assert!(String::from_utf8_lossy(&backend.screen).contains("a"));
+
+        backend
+            .draw(&frame(&[((0, 0), 'a'), ((1, 0), 'c')]))
+            .unwrap();
+        let second = String::from_utf8_lossy(&backend.screen[first..]).to_string();
+        assert_eq!(
+            second,
+            format!("{}c{}", termion::cursor::Goto(2, 1), termion::cursor::Hide)
+        );
+
+        let length = backend.screen.len();
+        backend
+            .draw(&frame(&[((0, 0), 'a'), ((1, 0), 'c')]).with_caption("Score"))
+            .unwrap();
+        let third = String::from_utf8_lossy(&backend.screen[length..]).to_string();
+        assert!(third.starts_with(&termion::clear::All.to_string()));
+        assert!(third.contains(&format!("{}a", termion::cursor::Goto(1, 2))));
+    }
+}
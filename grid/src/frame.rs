@@ -0,0 +1,121 @@
+use std::fmt::{Display, Formatter};
+
+use super::{Bounds, Position, Render, SparseGrid};
+
+// A grid flattened to characters, ready to hand to a backend. Overlays such as
+// cursors or paths are drawn on top with `set`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub caption: Option<String>,
+    bounds: Option<Bounds>,
+    cells: Vec<char>,
+}
+
+impl Frame {
+    pub fn new(bounds: Option<Bounds>, blank: char) -> Frame {
+        let size = bounds.map_or(0, |bounds| bounds.width() * bounds.height());
+        Frame {
+            caption: None,
+            bounds,
+            cells: vec![blank; size],
+        }
+    }
+
+    pub fn from_grid<T: Render>(grid: &SparseGrid<T>) -> Frame {
+        let mut frame = Frame::new(grid.bounds(), T::blank());
+        for (&position, value) in grid.iter() {
+            frame.set(position, value.render());
+        }
+        frame
+    }
+
+    pub fn with_caption(mut self, caption: &str) -> Frame {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    fn index(&self, position: Position) -> Option<usize> {
+        let bounds = self.bounds?;
+        if !bounds.contains(position) {
+            return None;
+        }
+        Some(
+            (position.1 - bounds.min_y) as usize * bounds.width()
+                + (position.0 - bounds.min_x) as usize,
+        )
+    }
+
+    pub fn get(&self, position: Position) -> Option<char> {
+        self.index(position).map(|index| self.cells[index])
+    }
+
+    // Positions outside the frame are ignored.
+    pub fn set(&mut self, position: Position, value: char) {
+        if let Some(index) = self.index(position) {
+            self.cells[index] = value;
+        }
+    }
+
+    pub fn rows(&self) -> Vec<String> {
+        match self.bounds {
+            Some(bounds) => self
+                .cells
+                .chunks(bounds.width())
+                .map(|row| row.iter().collect())
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        if let Some(caption) = &self.caption {
+            writeln!(f, "{}", caption)?;
+        }
+        for row in self.rows() {
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Wall;
+
+    impl Render for Wall {
+        fn render(&self) -> char {
+            '#'
+        }
+
+        fn blank() -> char {
+            '.'
+        }
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid: SparseGrid<Wall> = vec![((-1, 0), Wall), ((1, 1), Wall)].into_iter().collect();
+        let mut frame = Frame::from_grid(&grid).with_caption("Walls");
+        frame.set((0, 1), '@');
+        frame.set((5, 5), '@');
+        assert_eq!(frame.rows(), vec!["#..", ".@#"]);
+        assert_eq!(frame.get((1, 1)), Some('#'));
+        assert_eq!(frame.get((2, 1)), None);
+        assert_eq!(frame.to_string(), "Walls\n#..\n.@#\n");
+    }
+
+    #[test]
+    fn test_empty_grid() {
+        let frame = Frame::from_grid(&SparseGrid::<Wall>::new());
+        assert_eq!(frame.rows(), Vec::<String>::new());
+        assert_eq!(frame.to_string(), "");
+    }
+}
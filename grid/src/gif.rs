@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use super::image::Rgb;
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+const MAX_CODES: u16 = 4096;
+
+fn lzw(indices: &[u8], minimum_code_size: u32) -> Vec<u8> {
+    let clear = 1u16 << minimum_code_size;
+    let end = clear + 1;
+    let mut writer = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0,
+    };
+    let mut code_size = minimum_code_size + 1;
+    let mut next_code = end + 1;
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    writer.write(clear, code_size);
+
+    let mut pixels = indices.iter();
+    let mut prefix = match pixels.next() {
+        Some(&index) => u16::from(index),
+        None => {
+            writer.write(end, code_size);
+            return writer.finish();
+        }
+    };
+    for &index in pixels {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        if next_code == MAX_CODES {
+            writer.write(clear, code_size);
+            table.clear();
+            code_size = minimum_code_size + 1;
+            next_code = end + 1;
+        } else {
+            if next_code >= 1 << code_size {
+                code_size += 1;
+            }
+            table.insert((prefix, index), next_code);
+            next_code += 1;
+        }
+        prefix = u16::from(index);
+    }
+    writer.write(prefix, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+// Every frame covers the whole canvas and is made of indices into `colours`,
+// which can hold at most 256 entries. The delay is in hundredths of a second
+// and the animation loops forever.
+pub fn encode(
+    width: usize,
+    height: usize,
+    colours: &[Rgb],
+    frames: &[Vec<u8>],
+    delay: u16,
+) -> Vec<u8> {
+    let table_bits = (1..=8)
+        .find(|&bits| colours.len() <= 1 << bits)
+        .expect("GIF colour tables hold at most 256 colours");
+    let mut output = b"GIF89a".to_vec();
+    output.extend(&(width as u16).to_le_bytes());
+    output.extend(&(height as u16).to_le_bytes());
+    output.extend(&[0xf0 | (table_bits - 1) as u8, 0, 0]);
+    for index in 0..1 << table_bits {
+        output.extend(colours.get(index).unwrap_or(&[0, 0, 0]));
+    }
+    output.extend(&[0x21, 0xff, 0x0b]);
+    output.extend(b"NETSCAPE2.0");
+    output.extend(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    let minimum_code_size = table_bits.max(2);
+    for frame in frames {
+        output.extend(&[0x21, 0xf9, 0x04, 0x04]);
+        output.extend(&delay.to_le_bytes());
+        output.extend(&[0x00, 0x00]);
+
+        output.extend(&[0x2c, 0, 0, 0, 0]);
+        output.extend(&(width as u16).to_le_bytes());
+        output.extend(&(height as u16).to_le_bytes());
+        output.push(0);
+
+        output.push(minimum_code_size as u8);
+        for block in lzw(frame, minimum_code_size).chunks(255) {
+            output.push(block.len() as u8);
+            output.extend(block);
+        }
+        output.push(0);
+    }
+    output.push(0x3b);
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn decode_lzw(bytes: &[u8], minimum_code_size: u32) -> Vec<u8> {
+        let clear = 1usize << minimum_code_size;
+        let initial: Vec<Vec<u8>> = (0..clear + 2).map(|index| vec![index as u8]).collect();
+        let mut table = initial.clone();
+        let mut code_size = minimum_code_size + 1;
+        let mut previous: Option<usize> = None;
+        let mut output = vec![];
+        let mut bit = 0;
+        loop {
+            let code = (0..code_size as usize)
+                .map(|offset| {
+                    let position = bit + offset;
+                    ((bytes[position / 8] >> (position % 8)) as usize & 1) << offset
+                })
+                .sum::<usize>();
+            bit += code_size as usize;
+            if code == clear {
+                table = initial.clone();
+                code_size = minimum_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match previous {
+                None => table[code].clone(),
+                Some(previous) => {
+                    let entry = if code < table.len() {
+                        table[code].clone()
+                    } else {
+                        let mut entry = table[previous].clone();
+                        entry.push(table[previous][0]);
+                        entry
+                    };
+                    if table.len() < MAX_CODES as usize {
+                        let mut added = table[previous].clone();
+                        added.push(entry[0]);
+                        table.push(added);
+                        if table.len() == 1 << code_size && code_size < 12 {
+                            code_size += 1;
+                        }
+                    }
+                    entry
+                }
+            };
+            output.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let mut state = 7u32;
+        let noisy: Vec<u8> = (0..20000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8 % 5
+            })
+            .collect();
+        for (pixels, minimum_code_size) in [
+            (vec![0], 2),
+            (vec![1, 1, 1, 1, 1, 1, 1, 1], 2),
+            (noisy.clone(), 3),
+            (noisy.iter().map(|value| value * 50).collect(), 8),
+        ] {
+            assert_eq!(
+                decode_lzw(&lzw(&pixels, minimum_code_size), minimum_code_size),
+                pixels
+            );
+        }
+    }
+
+    // The 10x10 sample image from "What's in a GIF", whose image data was
+    // written by a separate encoder.
+    #[test]
+    fn test_lzw_known_encoding() {
+        let pixels: Vec<u8> = [
+            "1111122222",
+            "1111122222",
+            "1111122222",
+            "1110000222",
+            "1110000222",
+            "2220000111",
+            "2220000111",
+            "2222211111",
+            "2222211111",
+            "2222211111",
+        ]
+        .iter()
+        .flat_map(|row| row.bytes().map(|digit| digit - b'0'))
+        .collect();
+        let expected = [
+            0x8c, 0x2d, 0x99, 0x87, 0x2a, 0x1c, 0xdc, 0x33, 0xa0, 0x02, 0x75, 0xec, 0x95, 0xfa,
+            0xa8, 0xde, 0x60, 0x8c, 0x04, 0x91, 0x4c, 0x01,
+        ];
+        assert_eq!(lzw(&pixels, 2), expected);
+        assert_eq!(decode_lzw(&expected, 2), pixels);
+    }
+
+    #[test]
+    fn test_encode_structure() {
+        let gif = encode(2, 1, &[[0, 0, 0], [255, 255, 255]], &[vec![0, 1]], 5);
+        assert_eq!(gif[..6], *b"GIF89a");
+        assert_eq!(gif[6..11], [2, 0, 1, 0, 0xf0]);
+        assert_eq!(gif[13..19], [0, 0, 0, 255, 255, 255]);
+        assert_eq!(gif[gif.len() - 1], 0x3b);
+    }
+}
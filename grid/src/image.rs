@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
+
+use super::{png, Frame};
+
+pub type Rgb = [u8; 3];
+
+// Maps the characters of a frame to colours. Spaces that have not been given
+// a colour are background, and any other unknown character is foreground.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette {
+    pub background: Rgb,
+    pub foreground: Rgb,
+    colours: HashMap<char, Rgb>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::new([0, 0, 0], [255, 255, 255])
+    }
+}
+
+impl Palette {
+    pub fn new(background: Rgb, foreground: Rgb) -> Palette {
+        Palette {
+            background,
+            foreground,
+            colours: HashMap::new(),
+        }
+    }
+
+    pub fn with(mut self, value: char, colour: Rgb) -> Palette {
+        self.colours.insert(value, colour);
+        self
+    }
+
+    pub fn colour(&self, value: char) -> Rgb {
+        match self.colours.get(&value) {
+            Some(&colour) => colour,
+            None if value == ' ' => self.background,
+            None => self.foreground,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, colour: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![colour; width * height],
+        }
+    }
+
+    // Each cell of the frame becomes a `scale` x `scale` block of pixels. The
+    // caption is not drawn.
+    pub fn from_frame(frame: &Frame, palette: &Palette, scale: usize) -> Image {
+        let bounds = match frame.bounds() {
+            Some(bounds) => bounds,
+            None => return Image::new(0, 0, palette.background),
+        };
+        let mut image = Image::new(
+            bounds.width() * scale,
+            bounds.height() * scale,
+            palette.background,
+        );
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                let colour = palette.colour(frame.get((x, y)).unwrap());
+                image.fill(
+                    (x - bounds.min_x) as usize * scale,
+                    (y - bounds.min_y) as usize * scale,
+                    scale,
+                    colour,
+                );
+            }
+        }
+        image
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set_pixel(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    fn fill(&mut self, x: usize, y: usize, size: usize, colour: Rgb) {
+        for offset_y in 0..size {
+            for offset_x in 0..size {
+                self.set_pixel(x + offset_x, y + offset_y, colour);
+            }
+        }
+    }
+
+    // Copies `other` in with its top left corner at (x, y), clipping anything
+    // that falls outside.
+    pub fn draw(&mut self, other: &Image, x: usize, y: usize) {
+        for other_y in 0..other.height.min(self.height.saturating_sub(y)) {
+            for other_x in 0..other.width.min(self.width.saturating_sub(x)) {
+                self.set_pixel(x + other_x, y + other_y, other.pixel(other_x, other_y));
+            }
+        }
+    }
+
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|pixel| pixel.to_vec())
+            .collect()
+    }
+
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut output = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        output.extend(self.rgb_bytes());
+        output
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self.width, self.height, &self.rgb_bytes())
+    }
+
+    // The format is chosen from the extension, either .png or .ppm.
+    pub fn save(&self, path: &str) -> std::io::Result<()> {
+        let bytes = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unknown image format for {}", path),
+                ))
+            }
+        };
+        File::create(path)?.write_all(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::SparseGrid;
+
+    fn frame() -> Frame {
+        let grid: SparseGrid<char> = vec![((0, 0), '#'), ((1, 1), 'o')].into_iter().collect();
+        Frame::from_grid(&grid)
+    }
+
+    #[test]
+    fn test_from_frame() {
+        let palette = Palette::default().with('o', [255, 0, 0]);
+        let image = Image::from_frame(&frame(), &palette, 2);
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!(image.pixel(1, 1), [255, 255, 255]);
+        assert_eq!(image.pixel(2, 1), [0, 0, 0]);
+        assert_eq!(image.pixel(3, 3), [255, 0, 0]);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let image = Image::from_frame(&frame(), &Palette::default(), 1);
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend(&[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]);
+        assert_eq!(image.to_ppm(), expected);
+    }
+
+    #[test]
+    fn test_empty() {
+        let image = Image::from_frame(&Frame::new(None, ' '), &Palette::default(), 1);
+        assert_eq!((image.width, image.height), (0, 0));
+        assert_eq!(image.to_ppm(), b"P6\n0 0\n255\n".to_vec());
+        assert_eq!(image.to_png()[16..24], [0, 0, 0, 0, 0, 0, 0, 0]);
+        let image = Image::from_frame(&frame(), &Palette::default(), 0);
+        assert_eq!((image.width, image.height), (0, 0));
+        assert_eq!(
+            Image::new(0, 3, [0, 0, 0]).to_png()[16..24],
+            [0, 0, 0, 0, 0, 0, 0, 3]
+        );
+    }
+
+    #[test]
+    fn test_draw_clips() {
+        let mut canvas = Image::new(3, 2, [0, 0, 0]);
+        canvas.draw(&Image::new(2, 2, [9, 9, 9]), 2, 1);
+        assert_eq!(canvas.pixel(2, 1), [9, 9, 9]);
+        assert_eq!(canvas.pixel(1, 1), [0, 0, 0]);
+        assert_eq!(canvas.pixel(2, 0), [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_save_rejects_unknown_formats() {
+        let image = Image::new(1, 1, [0, 0, 0]);
+        assert_eq!(
+            image.save("image.bmp").unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+    }
+}
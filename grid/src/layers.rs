@@ -0,0 +1,179 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+
+// The values a layered image may hold, how each is drawn as text and as a
+// shade of grey, and which of them let the layers behind show through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerPalette {
+    colours: BTreeMap<u8, (char, u8)>,
+    transparent: BTreeSet<u8>,
+    // Drawn wherever nothing opaque covers a pixel.
+    background: (char, u8),
+}
+
+impl LayerPalette {
+    pub fn new() -> Self {
+        LayerPalette {
+            colours: BTreeMap::new(),
+            transparent: BTreeSet::new(),
+            background: ('.', 128),
+        }
+    }
+
+    pub fn with(mut self, value: u8, symbol: char, shade: u8) -> Self {
+        self.transparent.remove(&value);
+        self.colours.insert(value, (symbol, shade));
+        self
+    }
+
+    pub fn transparent(mut self, value: u8) -> Self {
+        self.colours.remove(&value);
+        self.transparent.insert(value);
+        self
+    }
+
+    pub fn background(mut self, symbol: char, shade: u8) -> Self {
+        self.background = (symbol, shade);
+        self
+    }
+
+    pub fn contains(&self, value: u8) -> bool {
+        self.colours.contains_key(&value) || self.transparent.contains(&value)
+    }
+
+    pub fn is_transparent(&self, value: u8) -> bool {
+        self.transparent.contains(&value)
+    }
+
+    fn colour(&self, value: Option<u8>) -> (char, u8) {
+        value
+            .and_then(|value| self.colours.get(&value).copied())
+            .unwrap_or(self.background)
+    }
+
+    pub fn symbol(&self, value: Option<u8>) -> char {
+        self.colour(value).0
+    }
+
+    pub fn shade(&self, value: Option<u8>) -> u8 {
+        self.colour(value).1
+    }
+
+    // The largest opaque value.
+    pub fn max(&self) -> Option<u8> {
+        self.colours.keys().next_back().copied()
+    }
+
+    // The opaque value closest to `value`, taking the larger of two that are
+    // equally close.
+    pub fn nearest(&self, value: u32) -> Option<u8> {
+        self.colours
+            .keys()
+            .copied()
+            .min_by_key(|&colour| (u32::from(colour).abs_diff(value), Reverse(colour)))
+    }
+}
+
+// Black, white and transparent as in the puzzle, with any other digit drawn
+// as itself in a shade between.
+impl Default for LayerPalette {
+    fn default() -> Self {
+        (3..=9)
+            .fold(LayerPalette::new(), |palette, digit| {
+                palette.with(digit, char::from(b'0' + digit), digit * 28)
+            })
+            .with(0, ' ', 0)
+            .with(1, '#', 255)
+            .transparent(2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Blend {
+    // The front-most opaque value, as in the puzzle.
+    #[default]
+    FirstOpaque,
+    LastOpaque,
+    // The sum of every opaque value, drawn as the nearest value in the
+    // palette so sums it doesn't hold never fall through to the background.
+    Additive,
+}
+
+impl Blend {
+    // Combines one pixel's values from front to back. None if they're all
+    // transparent.
+    pub fn apply<I: IntoIterator<Item = u8>>(
+        &self,
+        palette: &LayerPalette,
+        values: I,
+    ) -> Option<u8> {
+        let mut opaque = values
+            .into_iter()
+            .filter(|&value| !palette.is_transparent(value));
+        match self {
+            Blend::FirstOpaque => opaque.next(),
+            Blend::LastOpaque => opaque.last(),
+            Blend::Additive => opaque
+                .map(u32::from)
+                .reduce(|total, value| total + value)
+                .and_then(|total| palette.nearest(total)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_palette() {
+        let palette = LayerPalette::new()
+            .with(0, ' ', 0)
+            .with(5, '+', 100)
+            .with(9, '@', 200)
+            .transparent(7)
+            .background('~', 50);
+        assert!(palette.contains(7) && !palette.contains(1));
+        assert!(palette.is_transparent(7) && !palette.is_transparent(5));
+        assert_eq!(palette.symbol(Some(5)), '+');
+        assert_eq!(palette.symbol(None), '~');
+        assert_eq!((palette.shade(Some(9)), palette.shade(Some(7))), (200, 50));
+        assert_eq!(palette.max(), Some(9));
+        let default = LayerPalette::default();
+        assert!(default.is_transparent(2));
+        assert_eq!(default.symbol(Some(1)), '#');
+        assert_eq!(default.symbol(Some(8)), '8');
+        assert_eq!(default.shade(Some(1)), 255);
+    }
+
+    #[test]
+    fn test_blend() {
+        let palette = LayerPalette::new()
+            .with(0, ' ', 0)
+            .with(1, '-', 85)
+            .with(2, '=', 170)
+            .with(3, '#', 255)
+            .transparent(9);
+        let values = vec![9, 1, 9, 0, 2];
+        assert_eq!(Blend::FirstOpaque.apply(&palette, values.clone()), Some(1));
+        assert_eq!(Blend::LastOpaque.apply(&palette, values.clone()), Some(2));
+        assert_eq!(Blend::Additive.apply(&palette, values), Some(3));
+        assert_eq!(Blend::Additive.apply(&palette, vec![1, 1]), Some(2));
+        assert_eq!(Blend::FirstOpaque.apply(&palette, vec![9, 9]), None);
+        assert_eq!(Blend::LastOpaque.apply(&palette, vec![]), None);
+    }
+
+    #[test]
+    fn test_additive_gaps() {
+        let palette = LayerPalette::new()
+            .with(0, ' ', 0)
+            .with(3, '+', 100)
+            .with(9, '#', 255);
+        assert_eq!(palette.nearest(5), Some(3));
+        assert_eq!(palette.nearest(7), Some(9));
+        assert_eq!(Blend::Additive.apply(&palette, vec![3, 3]), Some(9));
+        assert_eq!(Blend::Additive.apply(&palette, vec![3, 0, 0]), Some(3));
+        assert_eq!(Blend::Additive.apply(&palette, vec![9, 9, 9]), Some(9));
+        assert_eq!(LayerPalette::new().nearest(4), None);
+    }
+}
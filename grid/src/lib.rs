@@ -0,0 +1,189 @@
+use std::collections::hash_map::{Iter, Values};
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+pub mod animation;
+pub mod backend;
+pub mod frame;
+mod gif;
+pub mod image;
+pub mod layers;
+pub mod ocr;
+mod png;
+
+pub use animation::Animation;
+pub use backend::{Backend, BufferBackend, TermionBackend, TextBackend};
+pub use frame::Frame;
+pub use image::{Image, Palette};
+pub use layers::{Blend, LayerPalette};
+
+pub type Position = (i64, i64);
+
+pub trait Render {
+    fn render(&self) -> char;
+
+    // Drawn for cells that have never been set.
+    fn blank() -> char
+    where
+        Self: Sized,
+    {
+        ' '
+    }
+}
+
+impl Render for char {
+    fn render(&self) -> char {
+        *self
+    }
+}
+
+// Inclusive on both ends, so a single cell has a width and height of 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub min_y: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    pub fn at(position: Position) -> Bounds {
+        Bounds {
+            min_x: position.0,
+            min_y: position.1,
+            max_x: position.0,
+            max_y: position.1,
+        }
+    }
+
+    pub fn include(&mut self, position: Position) {
+        self.min_x = self.min_x.min(position.0);
+        self.min_y = self.min_y.min(position.1);
+        self.max_x = self.max_x.max(position.0);
+        self.max_y = self.max_y.max(position.1);
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        (self.min_x..=self.max_x).contains(&position.0)
+            && (self.min_y..=self.max_y).contains(&position.1)
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y + 1) as usize
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Position, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn insert(&mut self, position: Position, value: T) -> Option<T> {
+        match self.bounds.as_mut() {
+            Some(bounds) => bounds.include(position),
+            None => self.bounds = Some(Bounds::at(position)),
+        }
+        self.cells.insert(position, value)
+    }
+
+    pub fn get(&self, position: &Position) -> Option<&T> {
+        self.cells.get(position)
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        self.cells.contains_key(position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The smallest box holding every cell ever set, or None if nothing has been.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn iter(&self) -> Iter<'_, Position, T> {
+        self.cells.iter()
+    }
+
+    pub fn values(&self) -> Values<'_, Position, T> {
+        self.cells.values()
+    }
+}
+
+impl<T> Extend<(Position, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Position, T)>>(&mut self, iter: I) {
+        for (position, value) in iter {
+            self.insert(position, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(Position, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Position, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bounds_tracking() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        grid.insert((2, 3), 'a');
+        assert_eq!(grid.bounds(), Some(Bounds::at((2, 3))));
+        grid.insert((-1, 5), 'b');
+        grid.insert((0, 4), 'c');
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min_x: -1,
+                min_y: 3,
+                max_x: 2,
+                max_y: 5
+            }
+        );
+        assert_eq!((bounds.width(), bounds.height()), (4, 3));
+        assert!(bounds.contains((0, 3)));
+        assert!(!bounds.contains((0, 6)));
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let mut grid: SparseGrid<i64> = vec![((0, 0), 1), ((1, 0), 2)].into_iter().collect();
+        assert_eq!(grid.insert((0, 0), 3), Some(1));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.get(&(0, 0)), Some(&3));
+        assert_eq!(grid.values().sum::<i64>(), 5);
+    }
+}
@@ -0,0 +1,231 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use super::Frame;
+
+// The block letters drawn by the puzzles, 6 rows tall and at most 5 columns
+// wide.
+const HEIGHT: usize = 6;
+const GLYPHS: [(char, [&str; HEIGHT]); 19] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('T', ["###", ".#.", ".#.", ".#.", ".#.", ".#."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[derive(Debug, PartialEq)]
+pub enum OcrError {
+    Empty,
+    WrongHeight { height: usize },
+    UnknownGlyph { index: usize, glyph: Vec<String> },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            OcrError::Empty => write!(f, "No letters to read"),
+            OcrError::WrongHeight { height } => {
+                write!(f, "Letters are {} rows tall, expected {}", height, HEIGHT)
+            }
+            OcrError::UnknownGlyph { index, glyph } => write!(
+                f,
+                "Unknown letter at position {}:\n{}",
+                index,
+                glyph.join("\n")
+            ),
+        }
+    }
+}
+
+impl Error for OcrError {}
+
+fn glyph_at(rows: &[&Vec<bool>], x: usize, width: usize) -> Vec<String> {
+    rows.iter()
+        .map(|row| {
+            (x..x + width)
+                .map(|x| if row.get(x) == Some(&true) { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+// Letters normally have an empty column after them, but the widest ones fill
+// their whole cell and touch the next letter, so the widest match wins.
+fn recognise(rows: &[&Vec<bool>], x: usize) -> Option<(char, usize)> {
+    GLYPHS
+        .iter()
+        .filter(|(_, glyph)| glyph_at(rows, x, glyph[0].len()) == glyph)
+        .map(|(letter, glyph)| (*letter, glyph[0].len()))
+        .max_by_key(|&(_, width)| width)
+}
+
+// Reads the letters from a bitmap given as rows of lit pixels. Empty rows and
+// columns around the text are ignored, so the letters can be anywhere.
+pub fn read(bitmap: &[Vec<bool>]) -> Result<String, OcrError> {
+    let lit_rows: Vec<&Vec<bool>> = bitmap
+        .iter()
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+    let height = lit_rows
+        .iter()
+        .rposition(|row| row.contains(&true))
+        .map_or(0, |last| last + 1);
+    if height == 0 {
+        return Err(OcrError::Empty);
+    }
+    if height != HEIGHT {
+        return Err(OcrError::WrongHeight { height });
+    }
+    let rows = &lit_rows[..HEIGHT];
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let column_lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if !column_lit(x) {
+            x += 1;
+            continue;
+        }
+        match recognise(rows, x) {
+            Some((letter, letter_width)) => {
+                letters.push(letter);
+                x += letter_width;
+            }
+            None => {
+                let end = (x..width).find(|&x| !column_lit(x)).unwrap_or(width);
+                return Err(OcrError::UnknownGlyph {
+                    index: letters.len(),
+                    glyph: glyph_at(rows, x, end - x),
+                });
+            }
+        }
+    }
+    Ok(letters)
+}
+
+// Reads the letters drawn in a frame with the character `lit`.
+pub fn read_frame(frame: &Frame, lit: char) -> Result<String, OcrError> {
+    let bitmap: Vec<Vec<bool>> = frame
+        .rows()
+        .iter()
+        .map(|row| row.chars().map(|value| value == lit).collect())
+        .collect();
+    read(&bitmap)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Bounds;
+
+    fn bitmap(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|value| value == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_every_glyph() {
+        let mut rows = vec![String::new(); HEIGHT];
+        for (_, glyph) in GLYPHS.iter() {
+            for (row, glyph_row) in rows.iter_mut().zip(glyph.iter()) {
+                row.push_str(glyph_row);
+                row.push('.');
+            }
+        }
+        let rows: Vec<&str> = rows.iter().map(|row| row.as_str()).collect();
+        let expected: String = GLYPHS.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(read(&bitmap(&rows)), Ok(expected));
+    }
+
+    #[test]
+    fn test_ignores_margins() {
+        let rows = [
+            "..........",
+            ".#..#.###.",
+            ".#..#..#..",
+            ".####..#..",
+            ".#..#..#..",
+            ".#..#..#..",
+            ".#..#.###.",
+            "..........",
+        ];
+        assert_eq!(read(&bitmap(&rows)), Ok("HI".to_string()));
+    }
+
+    #[test]
+    fn test_wide_letters_touch() {
+        let rows = [
+            "#...#####",
+            "#...##...",
+            ".#.#.###.",
+            "..#..#...",
+            "..#..#...",
+            "..#..####",
+        ];
+        assert_eq!(read(&bitmap(&rows)), Ok("YE".to_string()));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(read(&bitmap(&["....", "...."])), Err(OcrError::Empty));
+        assert_eq!(
+            read(&bitmap(&["#", "#", "#"])),
+            Err(OcrError::WrongHeight { height: 3 })
+        );
+        let rows = [
+            "####.#..#",
+            "#....#..#",
+            "###..#..#",
+            "#....#..#",
+            "#....#..#",
+            "####.####",
+        ];
+        assert_eq!(
+            read(&bitmap(&rows)),
+            Err(OcrError::UnknownGlyph {
+                index: 1,
+                glyph: vec![
+                    "#..#".to_string(),
+                    "#..#".to_string(),
+                    "#..#".to_string(),
+                    "#..#".to_string(),
+                    "#..#".to_string(),
+                    "####".to_string(),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_frame() {
+        let mut bounds = Bounds::at((0, 0));
+        bounds.include((4, 5));
+        let mut frame = Frame::new(Some(bounds), ' ');
+        let (_, glyph) = GLYPHS.iter().find(|(letter, _)| *letter == 'L').unwrap();
+        for (y, row) in glyph.iter().enumerate() {
+            for (x, value) in row.chars().enumerate() {
+                if value == '#' {
+                    frame.set((x as i64 + 1, y as i64), '█');
+                }
+            }
+        }
+        assert_eq!(read_frame(&frame, '█'), Ok("L".to_string()));
+    }
+}
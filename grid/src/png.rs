@@ -0,0 +1,120 @@
+// Just enough of PNG to write truecolour images: the pixel data is wrapped in
+// uncompressed deflate blocks, so no compression library is needed.
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    (b << 16) | a
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut output = vec![0x78, 0x01];
+    let mut blocks = data.chunks(65535).peekable();
+    if blocks.peek().is_none() {
+        output.extend(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        output.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let length = block.len() as u16;
+        output.extend(&length.to_le_bytes());
+        output.extend(&(!length).to_le_bytes());
+        output.extend(block);
+    }
+    output.extend(&adler32(data).to_be_bytes());
+    output
+}
+
+fn chunk(output: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    output.extend(&(data.len() as u32).to_be_bytes());
+    let start = output.len();
+    output.extend(kind);
+    output.extend(data);
+    let crc = crc32(&output[start..]);
+    output.extend(&crc.to_be_bytes());
+}
+
+pub fn encode(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    let mut output = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    let mut header = vec![];
+    header.extend(&(width as u32).to_be_bytes());
+    header.extend(&(height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlace.
+    header.extend(&[8, 2, 0, 0, 0]);
+    chunk(&mut output, b"IHDR", &header);
+
+    // Each row is prefixed by its filter type. An image with no pixels still
+    // gets an IDAT chunk, just one holding nothing but the filter bytes.
+    let stride = width * 3;
+    let mut scanlines = Vec::with_capacity((stride + 1) * height);
+    for y in 0..height {
+        scanlines.push(0);
+        scanlines.extend(&rgb[y * stride..(y + 1) * stride]);
+    }
+    chunk(&mut output, b"IDAT", &zlib_stored(&scanlines));
+    chunk(&mut output, b"IEND", &[]);
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_stored_blocks() {
+        let data: Vec<u8> = (0..70000).map(|i| i as u8).collect();
+        let stored = zlib_stored(&data);
+        assert_eq!(stored[2], 0);
+        assert_eq!(stored[3..5], [0xff, 0xff]);
+        assert_eq!(stored[2 + 5 + 65535], 1);
+        assert_eq!(stored.len(), 2 + 5 + 65535 + 5 + (70000 - 65535) + 4);
+    }
+
+    #[test]
+    fn test_encode() {
+        let png = encode(2, 1, &[255, 0, 0, 0, 0, 255]);
+        assert_eq!(
+            png[..8],
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+        );
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 2, 0, 0, 0, 1]);
+        assert_eq!(png[png.len() - 8..png.len() - 4], *b"IEND");
+    }
+
+    #[test]
+    fn test_encode_empty() {
+        let png = encode(0, 0, &[]);
+        assert_eq!(png[16..24], [0, 0, 0, 0, 0, 0, 0, 0]);
+        // IHDR is 25 bytes, then an IDAT holding an empty stored block.
+        assert_eq!(png[33..41], [0, 0, 0, 11, b'I', b'D', b'A', b'T']);
+        assert_eq!(png[41..52], [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]);
+        let png = encode(0, 2, &[]);
+        assert_eq!(png[33..37], [0, 0, 0, 13]);
+        assert_eq!(png[41..48], [0x78, 0x01, 1, 2, 0, 0xfd, 0xff]);
+        assert_eq!(png[48..50], [0, 0]);
+    }
+}
@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::read_to_string;
+
+// Known good answers, keyed by the input they were produced from so that a
+// different input is never checked against someone else's answer. Each line
+// of the file looks like:
+//
+//     day | part | input hash | answer
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpectedAnswer {
+    pub day: u32,
+    pub part: u32,
+    pub hash: u64,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AnswersError {
+    Malformed { line: usize, reason: String },
+    Duplicate { line: usize, day: u32, part: u32 },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match self {
+            AnswersError::Malformed { line, reason } => write!(f, "Line {}: {}", line, reason),
+            AnswersError::Duplicate { line, day, part } => write!(
+                f,
+                "Line {}: day {} part {} is already recorded for this input",
+                line, day, part
+            ),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+// 64 bit FNV-1a of the input, ignoring trailing whitespace.
+pub fn input_hash(input: &str) -> u64 {
+    input
+        .trim_end()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+fn parse_line(line: usize, text: &str) -> Result<ExpectedAnswer, AnswersError> {
+    let malformed = |reason: String| AnswersError::Malformed { line, reason };
+    let fields: Vec<&str> = text.split('|').map(|field| field.trim()).collect();
+    if fields.len() != 4 {
+        return Err(malformed(format!(
+            "Expected 4 fields, found {}",
+            fields.len()
+        )));
+    }
+    let number = |field: &str| {
+        field
+            .parse::<u32>()
+            .map_err(|_| malformed(format!("Invalid number {:?}", field)))
+    };
+    Ok(ExpectedAnswer {
+        day: number(fields[0])?,
+        part: number(fields[1])?,
+        hash: u64::from_str_radix(fields[2], 16)
+            .map_err(|_| malformed(format!("Invalid hash {:?}", fields[2])))?,
+        answer: fields[3].to_string(),
+    })
+}
+
+pub fn parse_answers(text: &str) -> Result<Vec<ExpectedAnswer>, AnswersError> {
+    let mut answers: Vec<ExpectedAnswer> = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let answer = parse_line(line_number, line)?;
+        if answers.iter().any(|other| {
+            (other.day, other.part, other.hash) == (answer.day, answer.part, answer.hash)
+        }) {
+            return Err(AnswersError::Duplicate {
+                line: line_number,
+                day: answer.day,
+                part: answer.part,
+            });
+        }
+        answers.push(answer);
+    }
+    Ok(answers)
+}
+
+pub fn load_answers(filename: &str) -> Result<Vec<ExpectedAnswer>, Box<dyn Error>> {
+    Ok(parse_answers(&read_to_string(filename)?)?)
+}
+
+pub fn expected<'a>(
+    answers: &'a [ExpectedAnswer],
+    day: u32,
+    part: u32,
+    input: &str,
+) -> Option<&'a str> {
+    let hash = input_hash(input);
+    answers
+        .iter()
+        .find(|answer| (answer.day, answer.part, answer.hash) == (day, part, hash))
+        .map(|answer| answer.answer.as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(input_hash("a\n"), input_hash("a"));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(
+            "# day | part | input hash | answer\n\
+             \n\
+             1 | 2 | af63dc4c8601ec8c | 42\n\
+             8 | 2 | af63dc4c8601ec8c | YEHEF\n",
+        )
+        .unwrap();
+        assert_eq!(
+            answers[0],
+            ExpectedAnswer {
+                day: 1,
+                part: 2,
+                hash: 0xaf63_dc4c_8601_ec8c,
+                answer: "42".to_string(),
+            }
+        );
+        assert_eq!(expected(&answers, 8, 2, "a"), Some("YEHEF"));
+        assert_eq!(expected(&answers, 8, 2, "b"), None);
+        assert_eq!(expected(&answers, 8, 1, "a"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_answers("1 | 1 | 42"),
+            Err(AnswersError::Malformed {
+                line: 1,
+                reason: "Expected 4 fields, found 3".to_string()
+            })
+        );
+        assert_eq!(
+            parse_answers("\none | 1 | ff | 42"),
+            Err(AnswersError::Malformed {
+                line: 2,
+                reason: "Invalid number \"one\"".to_string()
+            })
+        );
+        assert_eq!(
+            parse_answers("1 | 1 | xyz | 42"),
+            Err(AnswersError::Malformed {
+                line: 1,
+                reason: "Invalid hash \"xyz\"".to_string()
+            })
+        );
+        assert_eq!(
+            parse_answers("1 | 1 | ff | 42\n1 | 1 | ff | 43"),
+            Err(AnswersError::Duplicate {
+                line: 2,
+                day: 1,
+                part: 1
+            })
+        );
+    }
+}
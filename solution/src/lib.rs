@@ -0,0 +1,96 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+pub mod answers;
+
+// Each day's input is stored once, for both parts, in the shared cache at the
+// top of the workspace.
+pub const INPUTS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../inputs");
+
+pub fn input_path(day: u32) -> String {
+    format!("{}/day-{:02}.txt", INPUTS, day)
+}
+
+// What a solver hands back: most puzzles want a number, but a few spell out
+// their answer in letters.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+// Implemented by every day and part so they can be run and checked together.
+pub trait Solution {
+    fn day(&self) -> u32;
+    fn part(&self) -> u32;
+    fn solve(&self, input: &str) -> Result<Answer, Box<dyn Error>>;
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(other: i64) -> Self {
+        Answer::Number(other)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(other: i32) -> Self {
+        Answer::Number(i64::from(other))
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(other: u32) -> Self {
+        Answer::Number(i64::from(other))
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(other: u64) -> Self {
+        Answer::Number(other as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(other: usize) -> Self {
+        Answer::Number(other as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(other: String) -> Self {
+        Answer::Text(other)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(other: &str) -> Self {
+        Answer::Text(other.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from("ABC").to_string(), "ABC");
+    }
+
+    #[test]
+    fn test_input_path() {
+        assert!(input_path(3).ends_with("/inputs/day-03.txt"));
+        assert!(std::path::Path::new(&input_path(1)).is_file());
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::prelude::*;
+
+use super::lang::{get_ops, op_code_lookup};
+use super::IntcodeVM;
+
+// Host call available to every case, so opcode 10 can be checked too.
+pub const DOUBLE: i64 = 1;
+
+const PARAMETER_COUNTS: [(i64, u32); 11] = [
+    (1, 3),
+    (2, 3),
+    (3, 1),
+    (4, 1),
+    (5, 2),
+    (6, 2),
+    (7, 3),
+    (8, 3),
+    (9, 1),
+    (10, 3),
+    (99, 0),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub line: usize,
+    pub program: Vec<i64>,
+    pub inputs: Vec<i64>,
+    pub outputs: Vec<i64>,
+    pub memory: Vec<(usize, i64)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConformanceError {
+    Malformed {
+        line: usize,
+        reason: String,
+    },
+    OutputMismatch {
+        case: String,
+        expected: Vec<i64>,
+        actual: Vec<i64>,
+    },
+    MemoryMismatch {
+        case: String,
+        address: usize,
+        expected: i64,
+        actual: i64,
+    },
+}
+
+impl Display for ConformanceError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            ConformanceError::Malformed { line, reason } => {
+                write!(f, "Malformed case on line {}: {}", line, reason)
+            }
+            ConformanceError::OutputMismatch {
+                case,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: expected outputs {:?} but got {:?}",
+                case, expected, actual
+            ),
+            ConformanceError::MemoryMismatch {
+                case,
+                address,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: expected {} at address {} but found {}",
+                case, expected, address, actual
+            ),
+        }
+    }
+}
+
+impl Error for ConformanceError {}
+
+fn parse_list(field: &str, line: usize) -> Result<Vec<i64>, ConformanceError> {
+    if field.is_empty() {
+        return Ok(vec![]);
+    }
+    field
+        .split(',')
+        .map(|value| {
+            value
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| ConformanceError::Malformed {
+                    line,
+                    reason: format!("'{}' is not a number", value.trim()),
+                })
+        })
+        .collect()
+}
+
+fn parse_memory(field: &str, line: usize) -> Result<Vec<(usize, i64)>, ConformanceError> {
+    field
+        .split_whitespace()
+        .map(|check| {
+            let mut parts = check.splitn(2, '=');
+            let address = parts.next().and_then(|address| address.parse().ok());
+            let value = parts.next().and_then(|value| value.parse().ok());
+            match (address, value) {
+                (Some(address), Some(value)) => Ok((address, value)),
+                _ => Err(ConformanceError::Malformed {
+                    line,
+                    reason: format!("'{}' is not of the form address=value", check),
+                }),
+            }
+        })
+        .collect()
+}
+
+// One case per line: `name | program | inputs | outputs | address=value ...`.
+// Blank lines and lines starting with '#' are ignored.
+pub fn parse_cases(contents: &str) -> Result<Vec<Case>, ConformanceError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index + 1, text.trim()))
+        .filter(|(_, text)| !text.is_empty() && !text.starts_with('#'))
+        .map(|(line, text)| {
+            let fields: Vec<&str> = text.split('|').map(|field| field.trim()).collect();
+            if fields.len() != 5 {
+                return Err(ConformanceError::Malformed {
+                    line,
+                    reason: format!("expected 5 fields but found {}", fields.len()),
+                });
+            }
+            Ok(Case {
+                name: fields[0].to_string(),
+                line,
+                program: parse_list(fields[1], line)?,
+                inputs: parse_list(fields[2], line)?,
+                outputs: parse_list(fields[3], line)?,
+                memory: parse_memory(fields[4], line)?,
+            })
+        })
+        .collect()
+}
+
+pub fn load_cases(filename: &str) -> Result<Vec<Case>, Box<dyn Error>> {
+    let mut contents = String::new();
+    {
+        let mut file = File::open(filename)?;
+        file.read_to_string(&mut contents)?;
+    }
+    Ok(parse_cases(&contents)?)
+}
+
+struct Execution {
+    outputs: Vec<i64>,
+    memory: Vec<i64>,
+    instructions: Vec<i64>,
+}
+
+fn execute(case: &Case) -> Execution {
+    let mut vm = IntcodeVM::create(case.program.clone(), get_ops(), &op_code_lookup, None, None);
+    vm.io.input = Some(Box::new(case.inputs.iter().copied()));
+    vm.io
+        .register_host_call(DOUBLE, "double", Box::new(|value| value * 2));
+    let mut outputs = vec![];
+    let mut instructions = vec![];
+    loop {
+        instructions.push(vm.memory[vm.memory.instruction_pointer]);
+        match vm.step() {
+            Some(Some(output)) => outputs.push(output),
+            Some(None) => {}
+            None => break,
+        }
+    }
+    Execution {
+        outputs,
+        memory: vm.memory.memory,
+        instructions,
+    }
+}
+
+pub fn check(case: &Case) -> Result<(), ConformanceError> {
+    let execution = execute(case);
+    if execution.outputs != case.outputs {
+        return Err(ConformanceError::OutputMismatch {
+            case: case.name.clone(),
+            expected: case.outputs.clone(),
+            actual: execution.outputs,
+        });
+    }
+    for &(address, expected) in &case.memory {
+        let actual = *execution.memory.get(address).unwrap_or(&0);
+        if actual != expected {
+            return Err(ConformanceError::MemoryMismatch {
+                case: case.name.clone(),
+                address,
+                expected,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Every instruction word, opcode and parameter modes, that none of the cases
+// execute.
+pub fn uncovered_instructions(cases: &[Case]) -> Vec<i64> {
+    let parameters: HashMap<i64, u32> = PARAMETER_COUNTS.iter().copied().collect();
+    let executed: HashSet<i64> = cases
+        .iter()
+        .flat_map(|case| execute(case).instructions)
+        .map(|instruction| {
+            let count = parameters.get(&(instruction % 100)).copied().unwrap_or(0);
+            instruction % (100 * 10_i64.pow(count))
+        })
+        .collect();
+    PARAMETER_COUNTS
+        .iter()
+        .flat_map(|&(op_code, count)| {
+            (0..3_i64.pow(count)).map(move |combination| {
+                op_code
+                    + (0..count)
+                        .map(|parameter| {
+                            combination / 3_i64.pow(parameter) % 3 * 10_i64.pow(parameter + 2)
+                        })
+                        .sum::<i64>()
+            })
+        })
+        .filter(|instruction| !executed.contains(instruction))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CASE_FILES: [&str; 2] = ["conformance/opcodes.txt", "conformance/programs.txt"];
+
+    fn all_cases() -> Vec<Case> {
+        CASE_FILES
+            .iter()
+            .flat_map(|filename| load_cases(filename).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_conformance() {
+        let failures: Vec<String> = all_cases()
+            .iter()
+            .filter_map(|case| check(case).err())
+            .map(|error| error.to_string())
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_every_opcode_and_mode_is_covered() {
+        assert_eq!(uncovered_instructions(&all_cases()), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn test_parse_cases() {
+        let cases = parse_cases("# comment\n\necho | 3,0,4,0,99 | 7 | 7 | 0=7 4=99\n").unwrap();
+        assert_eq!(
+            cases,
+            vec![Case {
+                name: "echo".to_string(),
+                line: 3,
+                program: vec![3, 0, 4, 0, 99],
+                inputs: vec![7],
+                outputs: vec![7],
+                memory: vec![(0, 7), (4, 99)],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_cases("\nbroken | 1,x,3 | | |"),
+            Err(ConformanceError::Malformed {
+                line: 2,
+                reason: "'x' is not a number".to_string()
+            })
+        );
+        assert_eq!(
+            parse_cases("short | 99"),
+            Err(ConformanceError::Malformed {
+                line: 1,
+                reason: "expected 5 fields but found 2".to_string()
+            })
+        );
+        assert_eq!(
+            parse_cases("bad | 99 | | | 0:99"),
+            Err(ConformanceError::Malformed {
+                line: 1,
+                reason: "'0:99' is not of the form address=value".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_mismatches_are_reported() {
+        let case = parse_cases("wrong | 104,1,99 | | 2 |").unwrap().remove(0);
+        assert_eq!(
+            check(&case),
+            Err(ConformanceError::OutputMismatch {
+                case: "wrong".to_string(),
+                expected: vec![2],
+                actual: vec![1],
+            })
+        );
+        let case = parse_cases("wrong | 1101,1,1,5,99 | | | 5=3")
+            .unwrap()
+            .remove(0);
+        assert_eq!(
+            check(&case),
+            Err(ConformanceError::MemoryMismatch {
+                case: "wrong".to_string(),
+                address: 5,
+                expected: 3,
+                actual: 2,
+            })
+        );
+    }
+}
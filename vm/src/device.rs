@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use super::Device;
+
+// A width x height grid of values, addressed row by row from the start of the
+// range the framebuffer is attached to. Reading or writing past the last pixel
+// panics, as with the keyboard's registers.
+#[derive(Clone)]
+pub struct Framebuffer {
+    pub width: usize,
+    pub height: usize,
+    pixels: Rc<RefCell<Vec<i64>>>,
+}
+
+impl Framebuffer {
+    pub fn new(width: usize, height: usize) -> Framebuffer {
+        Framebuffer {
+            width,
+            height,
+            pixels: Rc::new(RefCell::new(vec![0; width * height])),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.width * self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> i64 {
+        self.pixels.borrow()[y * self.width + x]
+    }
+
+    pub fn rows(&self) -> Vec<Vec<i64>> {
+        self.pixels
+            .borrow()
+            .chunks(self.width)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+}
+
+impl Device<i64> for Framebuffer {
+    fn read(&mut self, offset: usize) -> i64 {
+        if offset >= self.size() {
+            panic!("Framebuffer read out of bounds: {}", offset);
+        }
+        self.pixels.borrow()[offset]
+    }
+
+    fn write(&mut self, offset: usize, value: i64) {
+        if offset >= self.size() {
+            panic!("Framebuffer write out of bounds: {}", offset);
+        }
+        self.pixels.borrow_mut()[offset] = value;
+    }
+}
+
+// Reading offset 0 takes the next pressed key (-1 when none are waiting) and
+// offset 1 gives the number of keys waiting. Writing to offset 1 clears them.
+#[derive(Clone, Default)]
+pub struct Keyboard {
+    keys: Rc<RefCell<VecDeque<i64>>>,
+}
+
+impl Keyboard {
+    pub fn new() -> Keyboard {
+        Keyboard::default()
+    }
+
+    pub fn press(&self, key: i64) {
+        self.keys.borrow_mut().push_back(key);
+    }
+
+    pub fn pending(&self) -> usize {
+        self.keys.borrow_mut().len()
+    }
+}
+
+impl Device<i64> for Keyboard {
+    fn read(&mut self, offset: usize) -> i64 {
+        let mut keys = self.keys.borrow_mut();
+        match offset {
+            0 => keys.pop_front().unwrap_or(-1),
+            1 => keys.len() as i64,
+            _ => panic!("Invalid keyboard register {}", offset),
+        }
+    }
+
+    fn write(&mut self, offset: usize, _value: i64) {
+        match offset {
+            0 => {}
+            1 => self.keys.borrow_mut().clear(),
+            _ => panic!("Invalid keyboard register {}", offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lang::load_from_str;
+
+    #[test]
+    fn test_framebuffer_writes() {
+        let framebuffer = Framebuffer::new(3, 2);
+        let mut vm = load_from_str("1101,3,4,104,1101,0,9,100,99").unwrap();
+        vm.memory
+            .attach_device(100..100 + framebuffer.size(), Box::new(framebuffer.clone()));
+        while vm.next().is_some() {}
+        assert_eq!(framebuffer.rows(), vec![vec![9, 0, 0], vec![0, 7, 0]]);
+        assert_eq!(framebuffer.pixel(1, 1), 7);
+        assert_eq!(vm.memory.memory.len(), 9);
+    }
+
+    #[test]
+    fn test_framebuffer_reads() {
+        let framebuffer = Framebuffer::new(2, 2);
+        let mut vm = load_from_str("1101,5,0,103,1,103,103,102,4,102,99").unwrap();
+        vm.memory
+            .attach_device(100..104, Box::new(framebuffer.clone()));
+        assert_eq!(vm.collect::<Vec<i64>>(), vec![10]);
+        assert_eq!(framebuffer.rows(), vec![vec![0, 0], vec![10, 5]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Framebuffer read out of bounds: 4")]
+    fn test_framebuffer_read_out_of_bounds() {
+        Framebuffer::new(2, 2).read(4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Framebuffer write out of bounds: 4")]
+    fn test_framebuffer_write_out_of_bounds() {
+        Framebuffer::new(2, 2).write(4, 1);
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let keyboard = Keyboard::new();
+        keyboard.press(65);
+        keyboard.press(66);
+        let mut vm = load_from_str("4,201,4,200,4,200,4,200,99").unwrap();
+        vm.memory
+            .attach_device(200..202, Box::new(keyboard.clone()));
+        assert_eq!(vm.collect::<Vec<i64>>(), vec![2, 65, 66, -1]);
+        assert_eq!(keyboard.pending(), 0);
+    }
+
+    #[test]
+    fn test_keyboard_to_framebuffer() {
+        let keyboard = Keyboard::new();
+        let framebuffer = Framebuffer::new(2, 1);
+        keyboard.press(1);
+        keyboard.press(2);
+        let mut vm = load_from_str("1,200,202,300,1,200,202,301,1101,0,0,201,99").unwrap();
+        vm.memory
+            .attach_device(200..202, Box::new(keyboard.clone()));
+        vm.memory
+            .attach_device(300..302, Box::new(framebuffer.clone()));
+        keyboard.press(3);
+        while vm.next().is_some() {}
+        assert_eq!(framebuffer.rows(), vec![vec![1, 2]]);
+        assert_eq!(keyboard.pending(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps existing device")]
+    fn test_overlapping_devices() {
+        let mut vm = load_from_str("99").unwrap();
+        vm.memory.attach_device(10..20, Box::new(Keyboard::new()));
+        vm.memory
+            .attach_device(19..25, Box::new(Framebuffer::new(2, 3)));
+    }
+}
@@ -0,0 +1,473 @@
+use std::cell::Cell;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe};
+use std::sync::Once;
+
+use super::host::random;
+use super::lang::{get_ops, op_code_lookup};
+use super::IntcodeVM;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HaltState {
+    Halted,
+    Faulted,
+    StepLimit,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outcome {
+    pub outputs: Vec<i64>,
+    pub memory: Vec<i64>,
+    pub halt: HaltState,
+}
+
+// A deliberately simple interpreter written straight from the puzzle text,
+// sharing no code with `lang`, to check the VM against. Like the VM, writes in
+// immediate mode go to the parameter itself.
+pub mod reference {
+    use super::{HaltState, Outcome};
+
+    // The VM uses plain arithmetic, which faults on overflow in debug builds
+    // and wraps in release ones.
+    fn add(a: i64, b: i64) -> Option<i64> {
+        if cfg!(debug_assertions) {
+            a.checked_add(b)
+        } else {
+            Some(a.wrapping_add(b))
+        }
+    }
+
+    fn mul(a: i64, b: i64) -> Option<i64> {
+        if cfg!(debug_assertions) {
+            a.checked_mul(b)
+        } else {
+            Some(a.wrapping_mul(b))
+        }
+    }
+
+    struct Machine {
+        memory: Vec<i64>,
+        ip: i64,
+        base: i64,
+    }
+
+    impl Machine {
+        fn read(&self, address: i64) -> Option<i64> {
+            if address < 0 {
+                return None;
+            }
+            Some(*self.memory.get(address as usize).unwrap_or(&0))
+        }
+
+        fn write(&mut self, address: i64, value: i64) -> Option<()> {
+            if address < 0 {
+                return None;
+            }
+            let address = address as usize;
+            if address >= self.memory.len() {
+                self.memory.resize(address + 1, 0);
+            }
+            self.memory[address] = value;
+            Some(())
+        }
+
+        fn mode(&self, parameter: u32) -> Option<i64> {
+            Some(self.read(self.ip)? / 10_i64.pow(parameter + 1) % 10)
+        }
+
+        fn address(&self, parameter: u32) -> Option<i64> {
+            let raw_position = self.ip + i64::from(parameter);
+            match self.mode(parameter)? {
+                0 => self.read(raw_position),
+                1 => Some(raw_position),
+                2 => add(self.base, self.read(raw_position)?),
+                _ => None,
+            }
+        }
+
+        fn value(&self, parameter: u32) -> Option<i64> {
+            self.read(self.address(parameter)?)
+        }
+    }
+
+    enum Step {
+        Continue(Option<i64>),
+        Halt,
+    }
+
+    fn step(machine: &mut Machine, inputs: &mut dyn Iterator<Item = i64>) -> Option<Step> {
+        if machine.ip < 0 || machine.ip as usize >= machine.memory.len() {
+            return None;
+        }
+        let op_code = machine.read(machine.ip)? % 100;
+        let mut output = None;
+        match op_code {
+            1 | 2 | 7 | 8 => {
+                let a = machine.value(1)?;
+                let b = machine.value(2)?;
+                let result = match op_code {
+                    1 => add(a, b)?,
+                    2 => mul(a, b)?,
+                    7 => (a < b) as i64,
+                    _ => (a == b) as i64,
+                };
+                let address = machine.address(3)?;
+                machine.write(address, result)?;
+                machine.ip += 4;
+            }
+            3 => {
+                let address = machine.address(1)?;
+                let value = inputs.next()?;
+                machine.write(address, value)?;
+                machine.ip += 2;
+            }
+            4 => {
+                output = Some(machine.value(1)?);
+                machine.ip += 2;
+            }
+            5 | 6 => {
+                let condition = machine.value(1)?;
+                let target = machine.value(2)?;
+                if (condition != 0) == (op_code == 5) {
+                    if target < 0 {
+                        return None;
+                    }
+                    machine.ip = target;
+                } else {
+                    machine.ip += 3;
+                }
+            }
+            9 => {
+                machine.base = add(machine.base, machine.value(1)?)?;
+                machine.ip += 2;
+            }
+            99 => return Some(Step::Halt),
+            _ => return None,
+        }
+        Some(Step::Continue(output))
+    }
+
+    pub fn run(program: &[i64], inputs: &[i64], max_steps: usize) -> Outcome {
+        let mut machine = Machine {
+            memory: program.to_vec(),
+            ip: 0,
+            base: 0,
+        };
+        let mut inputs = inputs.iter().copied();
+        let mut outputs = vec![];
+        let mut halt = HaltState::StepLimit;
+        for _ in 0..max_steps {
+            match step(&mut machine, &mut inputs) {
+                Some(Step::Continue(output)) => outputs.extend(output),
+                Some(Step::Halt) => {
+                    halt = HaltState::Halted;
+                    break;
+                }
+                None => {
+                    halt = HaltState::Faulted;
+                    break;
+                }
+            }
+        }
+        Outcome {
+            outputs,
+            memory: machine.memory,
+            halt,
+        }
+    }
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+// Runs `f`, catching any panic without the panic hook printing it. Only this
+// thread is silenced, so panics elsewhere, such as in other tests, are still
+// reported.
+fn catch_quietly<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    QUIET_HOOK.call_once(|| {
+        let previous = take_hook();
+        set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+    QUIET.with(|quiet| quiet.set(true));
+    let result = catch_unwind(AssertUnwindSafe(f));
+    QUIET.with(|quiet| quiet.set(false));
+    result
+}
+
+// Faults are caught and reported as a halt state rather than a panic.
+pub fn run_vm(program: &[i64], inputs: &[i64], max_steps: usize) -> Outcome {
+    let mut vm = IntcodeVM::create(program.to_vec(), get_ops(), &op_code_lookup, None, None);
+    vm.io.input = Some(Box::new(inputs.iter().copied()));
+    let mut outputs = vec![];
+    let mut halt = HaltState::StepLimit;
+    for _ in 0..max_steps {
+        match catch_quietly(|| vm.step()) {
+            Ok(Some(output)) => outputs.extend(output),
+            Ok(None) => {
+                halt = HaltState::Halted;
+                break;
+            }
+            Err(_) => {
+                halt = HaltState::Faulted;
+                break;
+            }
+        }
+    }
+    Outcome {
+        outputs,
+        memory: vm.memory.memory,
+        halt,
+    }
+}
+
+// Memory only ever grows on writes, so trailing zeros carry no information.
+fn trimmed(memory: &[i64]) -> &[i64] {
+    let length = memory.len() - memory.iter().rev().take_while(|&&x| x == 0).count();
+    &memory[..length]
+}
+
+pub fn outcomes_match(vm: &Outcome, reference: &Outcome) -> bool {
+    vm.outputs == reference.outputs
+        && vm.halt == reference.halt
+        && trimmed(&vm.memory) == trimmed(&reference.memory)
+}
+
+#[derive(Debug)]
+pub struct Mismatch {
+    pub program: Vec<i64>,
+    pub inputs: Vec<i64>,
+    pub vm: Outcome,
+    pub reference: Outcome,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "Program {:?} with inputs {:?}\nVM: {:?}\nReference: {:?}",
+            self.program, self.inputs, self.vm, self.reference
+        )
+    }
+}
+
+pub fn compare(program: &[i64], inputs: &[i64], max_steps: usize) -> Option<Mismatch> {
+    let vm = run_vm(program, inputs, max_steps);
+    let reference = reference::run(program, inputs, max_steps);
+    if outcomes_match(&vm, &reference) {
+        None
+    } else {
+        Some(Mismatch {
+            program: program.to_vec(),
+            inputs: inputs.to_vec(),
+            vm,
+            reference,
+        })
+    }
+}
+
+// Programs only use the nine puzzle op codes with modes valid for each
+// parameter, and jump targets mostly land on instruction boundaries.
+pub fn generate_program(seed: u64, max_instructions: usize) -> Vec<i64> {
+    let mut rng = random(seed);
+    let instruction_count = 1 + rng(max_instructions as i64) as usize;
+    let mut instructions: Vec<Vec<i64>> = vec![];
+    let mut jumps = vec![];
+    for _ in 0..instruction_count {
+        let op_code = 1 + rng(9);
+        let (parameters, writes): (u32, &[u32]) = match op_code {
+            1 | 2 | 7 | 8 => (3, &[3]),
+            3 => (1, &[1]),
+            4 | 9 => (1, &[]),
+            _ => (2, &[]),
+        };
+        let mut instruction = vec![0];
+        let mut full_op_code = op_code;
+        for parameter in 1..=parameters {
+            let mode = if writes.contains(&parameter) {
+                2 * rng(2)
+            } else {
+                rng(3)
+            };
+            full_op_code += mode * 10_i64.pow(parameter + 1);
+            instruction.push(match mode {
+                1 => rng(21) - 10,
+                2 => rng(24) - 4,
+                _ => rng(64),
+            });
+        }
+        instruction[0] = full_op_code;
+        if op_code == 5 || op_code == 6 {
+            jumps.push((instructions.len(), full_op_code / 1000 % 10));
+        }
+        instructions.push(instruction);
+    }
+    instructions.push(vec![99]);
+
+    let starts: Vec<i64> = instructions
+        .iter()
+        .scan(0, |position, instruction| {
+            let start = *position;
+            *position += instruction.len() as i64;
+            Some(start)
+        })
+        .collect();
+    for (index, mode) in jumps {
+        if mode == 1 && rng(4) != 0 {
+            instructions[index][2] = starts[rng(starts.len() as i64) as usize];
+        }
+    }
+
+    let mut program: Vec<i64> = instructions.into_iter().flatten().collect();
+    let data_length = rng(16);
+    program.extend((0..data_length).map(|_| rng(41) - 20));
+    program
+}
+
+pub fn generate_inputs(seed: u64) -> Vec<i64> {
+    let mut rng = random(seed ^ 0x5eed);
+    (0..rng(5)).map(|_| rng(201) - 100).collect()
+}
+
+fn simpler_values(value: i64) -> Vec<i64> {
+    let mut candidates = vec![0, 1, value / 2, value - value.signum()];
+    candidates.retain(|&candidate| candidate.abs() < value.abs());
+    candidates.dedup();
+    candidates
+}
+
+// Greedily removes runs of words and then pulls the remaining values towards
+// zero for as long as the program keeps failing.
+pub fn shrink(program: &[i64], failing: &dyn Fn(&[i64]) -> bool) -> Vec<i64> {
+    let mut current = program.to_vec();
+    loop {
+        let mut improved = false;
+
+        let mut chunk = current.len();
+        while chunk > 0 {
+            let mut start = 0;
+            while start + chunk <= current.len() {
+                let candidate: Vec<i64> = current[..start]
+                    .iter()
+                    .chain(current[start + chunk..].iter())
+                    .copied()
+                    .collect();
+                if !candidate.is_empty() && failing(&candidate) {
+                    current = candidate;
+                    improved = true;
+                } else {
+                    start += 1;
+                }
+            }
+            chunk -= 1;
+        }
+
+        for index in 0..current.len() {
+            for value in simpler_values(current[index]) {
+                let mut candidate = current.clone();
+                candidate[index] = value;
+                if failing(&candidate) {
+                    current = candidate;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+
+        if !improved {
+            return current;
+        }
+    }
+}
+
+pub fn fuzz(seed: u64, iterations: usize, max_instructions: usize) -> Option<Mismatch> {
+    const MAX_STEPS: usize = 1_000;
+    (0..iterations).find_map(|iteration| {
+        let program_seed = seed.wrapping_add(u64::try_from(iteration).unwrap());
+        let program = generate_program(program_seed, max_instructions);
+        let inputs = generate_inputs(program_seed);
+        compare(&program, &inputs, MAX_STEPS)?;
+        let minimal = shrink(&program, &|candidate| {
+            compare(candidate, &inputs, MAX_STEPS).is_some()
+        });
+        compare(&minimal, &inputs, MAX_STEPS)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reference_matches_day9_quine() {
+        let quine = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+        let outcome = reference::run(&quine, &[], 1_000);
+        assert_eq!(outcome.outputs, quine);
+        assert_eq!(outcome.halt, HaltState::Halted);
+        assert!(compare(&quine, &[], 1_000).is_none());
+    }
+
+    #[test]
+    fn test_faults_and_step_limits_match() {
+        assert_eq!(run_vm(&[3, 0, 99], &[], 10).halt, HaltState::Faulted);
+        assert_eq!(
+            reference::run(&[3, 0, 99], &[], 10).halt,
+            HaltState::Faulted
+        );
+        assert_eq!(run_vm(&[1105, 1, 0], &[], 10).halt, HaltState::StepLimit);
+        assert!(compare(&[1105, 1, 0], &[], 10).is_none());
+        assert!(compare(&[1101, 1, 2, -1, 99], &[], 10).is_none());
+        assert!(compare(&[1102, i64::MAX, 2, 0, 99], &[], 10).is_none());
+    }
+
+    #[test]
+    fn test_catch_quietly() {
+        assert!(catch_quietly(|| panic!("expected")).is_err());
+        assert_eq!(catch_quietly(|| 7).unwrap(), 7);
+        assert!(!QUIET.with(Cell::get));
+        // Other threads aren't silenced.
+        std::thread::spawn(|| QUIET.with(|quiet| assert!(!quiet.get())))
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_generated_programs_are_deterministic() {
+        assert_eq!(generate_program(7, 20), generate_program(7, 20));
+        assert!(generate_program(7, 20)[0] % 100 <= 9);
+    }
+
+    #[test]
+    fn test_shrink_finds_minimal_program() {
+        let program = [1101, 3, 4, 20, 104, 12, 1105, 1, 0, 4, 20, 99, 7];
+        let minimal = shrink(&program, &|candidate| {
+            reference::run(candidate, &[], 100).outputs.contains(&12)
+        });
+        assert_eq!(minimal, vec![104, 12]);
+    }
+
+    #[test]
+    fn test_vm_matches_reference() {
+        if let Some(mismatch) = fuzz(2019, 200, 24) {
+            panic!("VM disagrees with reference interpreter\n{}", mismatch);
+        }
+    }
+
+    // The long run, for `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_vm_matches_reference_thoroughly() {
+        if let Some(mismatch) = fuzz(2019, 3_000, 24) {
+            panic!("VM disagrees with reference interpreter\n{}", mismatch);
+        }
+    }
+}
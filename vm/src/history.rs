@@ -0,0 +1,444 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+
+use super::IntcodeVM;
+
+const MAX_CHECKPOINTS: usize = 64;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VmSnapshot<T> {
+    pub step: usize,
+    pub instruction_pointer: usize,
+    pub memory: Vec<T>,
+    pub metadata: Vec<T>,
+    inputs_consumed: usize,
+}
+
+pub(crate) struct UndoEntry<T> {
+    instruction_pointer: usize,
+    metadata: Vec<T>,
+    writes: Vec<(usize, T, usize)>,
+    inputs_consumed: usize,
+}
+
+struct InputLog<T> {
+    consumed: Vec<T>,
+    replay: VecDeque<T>,
+}
+
+// Records every value the program reads so that rewound steps read the same
+// input again when they are re-executed.
+struct RecordingInput<'a, T> {
+    inner: Box<dyn Iterator<Item = T> + 'a>,
+    log: Rc<RefCell<InputLog<T>>>,
+}
+
+impl<T> Iterator for RecordingInput<'_, T>
+where
+    T: Copy,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut log = self.log.borrow_mut();
+        let value = match log.replay.pop_front() {
+            Some(value) => value,
+            None => self.inner.next()?,
+        };
+        log.consumed.push(value);
+        Some(value)
+    }
+}
+
+// Undo log for the most recent steps, plus sparse snapshots that older steps
+// are re-executed from. Writes made through devices, host calls and output
+// callbacks are side effects outside the VM and are not rewound.
+pub struct History<T> {
+    pub step: usize,
+    checkpoint_interval: usize,
+    max_undo_entries: usize,
+    undo_log: VecDeque<UndoEntry<T>>,
+    // Addresses written by the latest step, kept even when the undo log
+    // isn't.
+    last_writes: Vec<usize>,
+    checkpoints: Vec<VmSnapshot<T>>,
+    inputs: Rc<RefCell<InputLog<T>>>,
+}
+
+impl<T> History<T> {
+    pub fn checkpoints(&self) -> &[VmSnapshot<T>] {
+        &self.checkpoints
+    }
+
+    pub fn undo_depth(&self) -> usize {
+        self.undo_log.len()
+    }
+}
+
+impl<'a, T> IntcodeVM<'a, T>
+where
+    T: 'a + Copy + Hash + Eq + PartialEq + Debug,
+{
+    // Input must be attached before history is enabled so that it is recorded.
+    pub fn enable_history(&mut self, checkpoint_interval: usize, max_undo_entries: usize) {
+        let inputs = Rc::new(RefCell::new(InputLog {
+            consumed: Vec::new(),
+            replay: VecDeque::new(),
+        }));
+        if let Some(inner) = self.io.input.take() {
+            self.io.input = Some(Box::new(RecordingInput {
+                inner,
+                log: inputs.clone(),
+            }));
+        }
+        self.history = Some(History {
+            step: 0,
+            checkpoint_interval: checkpoint_interval.max(1),
+            max_undo_entries,
+            undo_log: VecDeque::new(),
+            last_writes: Vec::new(),
+            checkpoints: Vec::new(),
+            inputs,
+        });
+        self.checkpoint();
+    }
+}
+
+impl<T> IntcodeVM<'_, T>
+where
+    T: Copy + Hash + Eq + PartialEq + Debug,
+{
+    pub fn snapshot(&self) -> VmSnapshot<T> {
+        VmSnapshot {
+            step: self.history.as_ref().map_or(0, |history| history.step),
+            instruction_pointer: self.memory.instruction_pointer,
+            memory: self.memory.memory.clone(),
+            metadata: self.memory.metadata.clone(),
+            inputs_consumed: self
+                .history
+                .as_ref()
+                .map_or(0, |history| history.inputs.borrow().consumed.len()),
+        }
+    }
+
+    pub(crate) fn begin_undo_entry(&mut self) -> Option<UndoEntry<T>> {
+        let inputs_consumed = self.history.as_ref()?.inputs.borrow().consumed.len();
+        self.memory.journal = Some(Vec::new());
+        Some(UndoEntry {
+            instruction_pointer: self.memory.instruction_pointer,
+            metadata: self.memory.metadata.clone(),
+            writes: Vec::new(),
+            inputs_consumed,
+        })
+    }
+
+    pub(crate) fn finish_undo_entry(&mut self, entry: Option<UndoEntry<T>>) {
+        if let Some(mut entry) = entry {
+            entry.writes = self.memory.journal.take().unwrap_or_default();
+            let history = self.history.as_mut().unwrap();
+            history.last_writes = entry
+                .writes
+                .iter()
+                .map(|&(position, _, _)| position)
+                .collect();
+            history.undo_log.push_back(entry);
+            if history.undo_log.len() > history.max_undo_entries {
+                history.undo_log.pop_front();
+            }
+            history.step += 1;
+            if history.step.is_multiple_of(history.checkpoint_interval) {
+                self.checkpoint();
+            }
+        }
+    }
+
+    fn checkpoint(&mut self) {
+        let snapshot = self.snapshot();
+        let history = self.history.as_mut().unwrap();
+        history.checkpoints.push(snapshot);
+        if history.checkpoints.len() > MAX_CHECKPOINTS {
+            history.checkpoint_interval *= 2;
+            let interval = history.checkpoint_interval;
+            history
+                .checkpoints
+                .retain(|checkpoint| checkpoint.step.is_multiple_of(interval));
+        }
+    }
+
+    fn undo(&mut self) -> bool {
+        let history = self.history.as_mut().expect("History is not enabled");
+        let entry = match history.undo_log.pop_back() {
+            Some(entry) => entry,
+            None => return false,
+        };
+        for &(position, value, length) in entry.writes.iter().rev() {
+            if position < self.memory.memory.len() {
+                self.memory.memory[position] = value;
+            }
+            self.memory.memory.truncate(length);
+        }
+        self.memory.instruction_pointer = entry.instruction_pointer;
+        self.memory.metadata = entry.metadata;
+
+        let mut inputs = history.inputs.borrow_mut();
+        while inputs.consumed.len() > entry.inputs_consumed {
+            let value = inputs.consumed.pop().unwrap();
+            inputs.replay.push_front(value);
+        }
+        drop(inputs);
+
+        history.step -= 1;
+        let step = history.step;
+        history
+            .checkpoints
+            .retain(|checkpoint| checkpoint.step <= step);
+        true
+    }
+
+    // False if there's no such checkpoint.
+    pub fn rewind(&mut self, checkpoint: usize) -> bool {
+        let history = self.history.as_mut().expect("History is not enabled");
+        let snapshot = match history.checkpoints.get(checkpoint) {
+            Some(snapshot) => snapshot.clone(),
+            None => return false,
+        };
+        history.checkpoints.truncate(checkpoint + 1);
+
+        let mut inputs = history.inputs.borrow_mut();
+        let rewound: Vec<T> = inputs.consumed.drain(snapshot.inputs_consumed..).collect();
+        for value in rewound.into_iter().rev() {
+            inputs.replay.push_front(value);
+        }
+        drop(inputs);
+
+        history.step = snapshot.step;
+        history.undo_log.clear();
+        history.last_writes.clear();
+        self.memory.instruction_pointer = snapshot.instruction_pointer;
+        self.memory.memory = snapshot.memory;
+        self.memory.metadata = snapshot.metadata;
+        true
+    }
+
+    fn current_step(&self) -> usize {
+        self.history.as_ref().expect("History is not enabled").step
+    }
+
+    // Output callbacks are not called for steps executed while travelling.
+    fn run_silently_to(&mut self, target: usize) -> bool {
+        let output = self.io.output.take();
+        let mut reached = true;
+        while self.current_step() < target {
+            if self.step().is_none() {
+                reached = false;
+                break;
+            }
+        }
+        self.io.output = output;
+        reached
+    }
+
+    pub fn goto_step(&mut self, target: usize) -> bool {
+        let step = self.current_step();
+        if target >= step {
+            return self.run_silently_to(target);
+        }
+        let history = self.history.as_ref().unwrap();
+        if step - target <= history.undo_log.len() {
+            for _ in target..step {
+                self.undo();
+            }
+            return true;
+        }
+        let checkpoint = history
+            .checkpoints
+            .iter()
+            .rposition(|checkpoint| checkpoint.step <= target)
+            .expect("No checkpoint before target step");
+        self.rewind(checkpoint) && self.run_silently_to(target)
+    }
+
+    pub fn step_back(&mut self) -> bool {
+        let step = self.current_step();
+        step > 0 && self.goto_step(step - 1)
+    }
+
+    fn last_logged_write(&self, address: usize) -> Option<usize> {
+        let history = self.history.as_ref().unwrap();
+        history
+            .undo_log
+            .iter()
+            .rposition(|entry| {
+                entry
+                    .writes
+                    .iter()
+                    .any(|&(position, _, _)| position == address)
+            })
+            .map(|index| history.step - history.undo_log.len() + index + 1)
+    }
+
+    // Travels back to the state just after the most recent step that wrote to
+    // the address, returning that step. The VM is left unchanged if the
+    // address was never written.
+    pub fn rewind_to_last_write(&mut self, address: usize) -> Option<usize> {
+        if let Some(step) = self.last_logged_write(address) {
+            self.goto_step(step);
+            return Some(step);
+        }
+
+        let original_step = self.current_step();
+        let mut searched_from = original_step - self.history.as_ref().unwrap().undo_log.len();
+        while searched_from > 0 {
+            let history = self.history.as_ref().unwrap();
+            let checkpoint = history
+                .checkpoints
+                .iter()
+                .rposition(|checkpoint| checkpoint.step < searched_from)
+                .expect("No checkpoint before searched steps");
+            let segment_start = history.checkpoints[checkpoint].step;
+            self.rewind(checkpoint);
+
+            // Replayed steps are checked as they run, as the undo log may be
+            // too short to hold them, or not kept at all.
+            let mut found = None;
+            while self.current_step() < searched_from
+                && self.run_silently_to(self.current_step() + 1)
+            {
+                let history = self.history.as_ref().unwrap();
+                if history.last_writes.contains(&address) {
+                    found = Some(self.current_step());
+                }
+            }
+            if let Some(step) = found {
+                self.goto_step(step);
+                return Some(step);
+            }
+            searched_from = segment_start;
+        }
+        self.goto_step(original_step);
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lang::load_from_str;
+
+    const QUINE: &str = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+
+    fn state(vm: &IntcodeVM<i64>) -> (usize, Vec<i64>, Vec<i64>) {
+        (
+            vm.memory.instruction_pointer,
+            vm.memory.memory.clone(),
+            vm.memory.metadata.clone(),
+        )
+    }
+
+    #[test]
+    fn test_step_back_to_start() {
+        let mut vm = load_from_str("1,0,0,0,2,0,0,7,99").unwrap();
+        let initial = state(&vm);
+        vm.enable_history(10, 100);
+        while vm.next().is_some() {}
+        assert_eq!(vm.memory.memory, vec!(2, 0, 0, 0, 2, 0, 0, 4, 99));
+        assert!(vm.step_back());
+        assert_eq!(vm.memory.memory[7], 7);
+        assert!(vm.step_back());
+        assert_eq!(state(&vm), initial);
+        assert!(!vm.step_back());
+    }
+
+    #[test]
+    fn test_goto_any_step() {
+        let mut vm = load_from_str(QUINE).unwrap();
+        vm.enable_history(7, 3);
+        let mut states = vec![state(&vm)];
+        while vm.step().is_some() {
+            states.push(state(&vm));
+        }
+        assert!(vm.history.as_ref().unwrap().undo_depth() <= 3);
+        for &target in &[0, 50, 3, 40, 41, 39, 80, 2, states.len() - 1] {
+            assert!(vm.goto_step(target));
+            assert_eq!(state(&vm), states[target], "step {}", target);
+        }
+    }
+
+    #[test]
+    fn test_rewound_input_is_replayed() {
+        let mut vm = load_from_str("3,0,3,1,1,0,1,2,4,2,99").unwrap();
+        vm.io.input = Some(Box::new(vec![3, 4].into_iter()));
+        vm.enable_history(1, 1);
+        assert_eq!(vm.next(), Some(7));
+        assert!(vm.goto_step(1));
+        assert_eq!(vm.memory.memory[0], 3);
+        assert_eq!(vm.next(), Some(7));
+        assert!(vm.goto_step(0));
+        assert_eq!(vm.collect::<Vec<i64>>(), vec!(7));
+    }
+
+    #[test]
+    fn test_rewind_to_checkpoint() {
+        let mut vm = load_from_str(QUINE).unwrap();
+        vm.enable_history(10, 5);
+        let outputs: Vec<i64> = vm.by_ref().take(4).collect();
+        let checkpoint = vm.history.as_ref().unwrap().checkpoints()[1].clone();
+        assert!(!vm.rewind(2));
+        assert!(vm.rewind(1));
+        assert_eq!(vm.snapshot(), checkpoint);
+        assert_eq!(vm.history.as_ref().unwrap().step, 10);
+        vm.goto_step(0);
+        assert_eq!(vm.take(4).collect::<Vec<i64>>(), outputs);
+    }
+
+    #[test]
+    fn test_rewind_to_last_write() {
+        let mut vm = load_from_str(QUINE).unwrap();
+        vm.enable_history(4, 2);
+        while vm.step().is_some() {}
+        let end = vm.history.as_ref().unwrap().step;
+
+        // The counter at 100 is last incremented by the third last step.
+        assert_eq!(vm.rewind_to_last_write(100), Some(end - 2));
+        assert_eq!(vm.memory.memory[100], 16);
+        assert_eq!(vm.memory.instruction_pointer, 8);
+
+        // The quine only ever writes to 100 and 101.
+        assert_eq!(vm.rewind_to_last_write(5), None);
+        assert_eq!(vm.history.as_ref().unwrap().step, end - 2);
+
+        vm.goto_step(12);
+        assert_eq!(vm.rewind_to_last_write(100), Some(8));
+        assert_eq!(vm.memory.memory[100], 2);
+    }
+
+    #[test]
+    fn test_rewind_to_last_write_without_undo_log() {
+        let mut vm = load_from_str(QUINE).unwrap();
+        vm.enable_history(4, 0);
+        while vm.step().is_some() {}
+        let end = vm.history.as_ref().unwrap().step;
+        assert_eq!(vm.history.as_ref().unwrap().undo_depth(), 0);
+        assert_eq!(vm.rewind_to_last_write(100), Some(end - 2));
+        assert_eq!(vm.memory.memory[100], 16);
+        assert_eq!(vm.rewind_to_last_write(5), None);
+        assert_eq!(vm.history.as_ref().unwrap().step, end - 2);
+    }
+
+    #[test]
+    fn test_checkpoints_are_bounded() {
+        let mut vm = load_from_str("1101,0,0,7,1105,1,0").unwrap();
+        vm.enable_history(1, 10);
+        for _ in 0..10_000 {
+            vm.step();
+        }
+        let history = vm.history.as_ref().unwrap();
+        assert!(history.checkpoints().len() <= MAX_CHECKPOINTS);
+        assert_eq!(history.checkpoints()[0].step, 0);
+        assert!(vm.goto_step(1233));
+        assert_eq!(vm.memory.instruction_pointer, 4);
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Bytes, Read};
+use std::time::Instant;
+
+use super::IntcodeVMIO;
+
+pub const RANDOM: i64 = 1;
+pub const CLOCK: i64 = 2;
+pub const READ: i64 = 3;
+
+// xorshift64*: returns a value in 0..bound, or any non-negative value when
+// the bound is not positive.
+pub fn random(seed: u64) -> impl FnMut(i64) -> i64 {
+    let mut state = seed.max(1);
+    move |bound| {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        let value = (state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 1) as i64;
+        if bound > 0 {
+            value % bound
+        } else {
+            value
+        }
+    }
+}
+
+// Milliseconds elapsed since the clock was created, the argument is ignored.
+pub fn clock() -> impl FnMut(i64) -> i64 {
+    let start = Instant::now();
+    move |_| start.elapsed().as_millis() as i64
+}
+
+// Reads the next byte of the file with the given handle (its index in
+// `paths`), or -1 once the file is exhausted or cannot be opened.
+pub fn file_reader(paths: Vec<String>) -> impl FnMut(i64) -> i64 {
+    let mut open_files: HashMap<i64, Option<Bytes<BufReader<File>>>> = HashMap::new();
+    move |handle| {
+        let file = open_files.entry(handle).or_insert_with(|| {
+            paths
+                .get(handle as usize)
+                .and_then(|path| File::open(path).ok())
+                .map(|file| BufReader::new(file).bytes())
+        });
+        match file.as_mut().and_then(|bytes| bytes.next()) {
+            Some(Ok(byte)) => i64::from(byte),
+            _ => -1,
+        }
+    }
+}
+
+pub fn register_standard_host_calls(io: &mut IntcodeVMIO<i64>, seed: u64, files: Vec<String>) {
+    io.register_host_call(RANDOM, "random", Box::new(random(seed)));
+    io.register_host_call(CLOCK, "clock", Box::new(clock()));
+    io.register_host_call(READ, "read", Box::new(file_reader(files)));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solution::input_path;
+
+    #[test]
+    fn test_random_is_deterministic_and_bounded() {
+        let mut first_rng = random(42);
+        let mut second_rng = random(42);
+        let first: Vec<i64> = (0..100).map(|_| first_rng(10)).collect();
+        let second: Vec<i64> = (0..100).map(|_| second_rng(10)).collect();
+        assert_eq!(first, second);
+        assert!(first.iter().all(|&value| (0..10).contains(&value)));
+    }
+
+    #[test]
+    fn test_file_reader() {
+        let mut read = file_reader(vec![input_path(4)]);
+        let contents: Vec<i64> = (0..14).map(|_| read(0)).collect();
+        let expected: Vec<i64> = b"357253-892942\n".iter().map(|&b| i64::from(b)).collect();
+        assert_eq!(contents, expected);
+        assert_eq!(read(0), -1);
+        assert_eq!(read(1), -1);
+    }
+}
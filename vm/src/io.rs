@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
 use std::io::stdin;
 use std::marker::PhantomData;
@@ -26,9 +27,9 @@ where
     }
 }
 
-pub fn create_stdio_vmio<'a, T: 'a>() -> IntcodeVMIO<'a, T>
+pub fn create_stdio_vmio<'a, T>() -> IntcodeVMIO<'a, T>
 where
-    T: Display + FromStr,
+    T: 'a + Display + FromStr,
     <T as FromStr>::Err: Debug,
 {
     IntcodeVMIO {
@@ -36,5 +37,6 @@ where
             phantom: PhantomData,
         })),
         output: Some(Box::new(|x| println!("Output: {}", x))),
+        host_calls: HashMap::new(),
     }
 }
@@ -4,6 +4,7 @@ use std::io::prelude::*;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
+use super::loader::{self, LoadError};
 use super::{IntcodeVM, IntcodeVMIO, IntcodeVMMemory, OpCode};
 
 fn get_parameter_addresses_with_modes(
@@ -21,7 +22,7 @@ fn get_parameter_addresses_with_modes(
                 }
                 1 => memory.instruction_pointer + offset + 1,
                 2 => usize::try_from(
-                    *memory.metadata.get(0).unwrap_or(&0)
+                    *memory.metadata.first().unwrap_or(&0)
                         + memory.get(memory.instruction_pointer + offset + 1, 0),
                 )
                 .unwrap(),
@@ -99,14 +100,12 @@ impl OpCode<i64> for Output {
         memory: &mut IntcodeVMMemory<i64>,
         io: &mut IntcodeVMIO<i64>,
     ) -> Option<(usize, Option<i64>)> {
-        let addresses = get_parameter_addresses_with_modes(memory, 2);
+        let addresses = get_parameter_addresses_with_modes(memory, 1);
+        let value = memory.get(addresses[0], 0);
         if let Some(function) = io.output.as_mut() {
-            function(memory.get(addresses[0], 0));
+            function(value);
         }
-        Some((
-            memory.instruction_pointer + 2,
-            Some(memory.get(addresses[0], 0)),
-        ))
+        Some((memory.instruction_pointer + 2, Some(value)))
     }
 }
 
@@ -189,6 +188,25 @@ impl OpCode<i64> for RelativeBaseOffset {
     }
 }
 
+struct CallHost;
+impl OpCode<i64> for CallHost {
+    fn execute(
+        &self,
+        memory: &mut IntcodeVMMemory<i64>,
+        io: &mut IntcodeVMIO<i64>,
+    ) -> Option<(usize, Option<i64>)> {
+        let addresses = get_parameter_addresses_with_modes(memory, 3);
+        let id = memory.get(addresses[0], 0);
+        let argument = memory.get(addresses[1], 0);
+        let host_call = io
+            .host_calls
+            .get_mut(&id)
+            .unwrap_or_else(|| panic!("Unknown host call {}", id));
+        memory.set(addresses[2], (host_call.function)(argument), 0);
+        Some((memory.instruction_pointer + 4, None))
+    }
+}
+
 pub fn get_ops() -> HashMap<i64, Box<dyn OpCode<i64>>> {
     let mut ops: HashMap<i64, Box<dyn OpCode<i64>>> = HashMap::new();
     ops.insert(1, Box::new(Add));
@@ -200,32 +218,31 @@ pub fn get_ops() -> HashMap<i64, Box<dyn OpCode<i64>>> {
     ops.insert(7, Box::new(LessThan));
     ops.insert(8, Box::new(Equals));
     ops.insert(9, Box::new(RelativeBaseOffset));
+    ops.insert(10, Box::new(CallHost));
     ops.insert(99, Box::new(Halt));
     ops
 }
 
-fn string_to_i64_list(data: &str) -> Result<Vec<i64>, std::num::ParseIntError> {
-    data.split(',')
-        .map(|x| x.parse::<i64>())
-        .collect::<Result<Vec<i64>, std::num::ParseIntError>>()
+fn string_to_i64_list(data: &str) -> Result<Vec<i64>, LoadError> {
+    loader::parse_text(data)
 }
 
 pub fn load_memory_from_file(filename: &str) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
-    let mut contents = String::new();
+    let mut contents = vec![];
     {
         let mut file = File::open(filename)?;
-        file.read_to_string(&mut contents)?;
+        file.read_to_end(&mut contents)?;
     }
-    Ok(string_to_i64_list(contents.trim())?)
+    Ok(loader::parse(&contents)?)
 }
 
 pub fn op_code_lookup(input: i64) -> i64 {
     input % 100
 }
 
-pub fn load_from_str(program: &str) -> Result<IntcodeVM<i64>, Box<dyn std::error::Error>> {
+pub fn load_from_str(program: &str) -> Result<IntcodeVM<'_, i64>, Box<dyn std::error::Error>> {
     Ok(IntcodeVM::create(
-        string_to_i64_list(program.trim())?,
+        string_to_i64_list(program)?,
         get_ops(),
         &op_code_lookup,
         None,
@@ -233,7 +250,15 @@ pub fn load_from_str(program: &str) -> Result<IntcodeVM<i64>, Box<dyn std::error
     ))
 }
 
-pub fn load_from_file(filename: &str) -> Result<IntcodeVM<i64>, Box<dyn std::error::Error>> {
+// Runs a program to completion, returning its outputs and final memory.
+pub fn run_program(program: &[i64], inputs: &[i64]) -> (Vec<i64>, Vec<i64>) {
+    let mut vm = IntcodeVM::create(program.to_vec(), get_ops(), &op_code_lookup, None, None);
+    vm.io.input = Some(Box::new(inputs.iter().copied()));
+    let outputs = vm.by_ref().collect();
+    (outputs, vm.memory.memory)
+}
+
+pub fn load_from_file(filename: &str) -> Result<IntcodeVM<'_, i64>, Box<dyn std::error::Error>> {
     Ok(IntcodeVM::create(
         load_memory_from_file(filename)?,
         get_ops(),
@@ -247,28 +272,53 @@ pub fn load_from_file(filename: &str) -> Result<IntcodeVM<i64>, Box<dyn std::err
 mod test {
     use super::load_from_file;
     use super::load_from_str;
+    use super::load_memory_from_file;
+    use super::loader::{self, Format};
+    use super::run_program;
     use super::string_to_i64_list;
+    use crate::program::Program;
+    use solution::input_path;
+
+    const COMPARE_TO_8: [i64; 47] = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
 
     #[test]
     fn string_to_i64_list_testcase() {
         assert_eq!(string_to_i64_list("12,14").unwrap(), vec!(12, 14))
     }
 
+    #[test]
+    fn test_load_from_file_detects_format() {
+        let text = load_memory_from_file(&input_path(2)).unwrap();
+        for &format in &[Format::Binary, Format::Varint] {
+            let path = std::env::temp_dir().join(format!("day-02-{:?}.intcode", format));
+            std::fs::write(&path, loader::encode(&text, format)).unwrap();
+            assert_eq!(load_memory_from_file(path.to_str().unwrap()).unwrap(), text);
+        }
+    }
+
     // Regression tests
     #[test]
     fn test_regression_day2_part1() {
-        let mut vm = load_from_file("../day-02/part-1/input.txt").unwrap();
-        while let Some(_) = vm.next() {}
+        let path = input_path(2);
+        let mut vm = load_from_file(&path).unwrap();
+        for _ in vm.by_ref() {}
         let last_memory = vm.memory.memory;
         assert_eq!(last_memory[0], 9_581_917);
     }
 
     #[test]
     fn test_regression_day2_part2() {
-        let mut vm = load_from_file("../day-02/part-2/input.txt").unwrap();
-        vm.memory[1] = 25;
-        vm.memory[2] = 5;
-        while let Some(_) = vm.next() {}
+        let mut vm = Program::from_file(&input_path(2))
+            .unwrap()
+            .variant()
+            .noun(25)
+            .verb(5)
+            .instantiate();
+        for _ in vm.by_ref() {}
         let last_memory = vm.memory.memory;
         assert_eq!(last_memory[0], 19_690_720);
     }
@@ -276,16 +326,17 @@ mod test {
     // Day 5 part 1
     #[test]
     fn test_parameter_mode_1() {
-        let mut vm = load_from_str("1002,4,3,4,33").unwrap();
-        while let Some(_) = vm.next() {}
-        let last_memory = vm.memory.memory;
-        assert_eq!(last_memory, vec!(1002, 4, 3, 4, 99));
+        assert_eq!(
+            run_program(&[1002, 4, 3, 4, 33], &[]).1,
+            vec![1002, 4, 3, 4, 99]
+        );
     }
 
     // regression
     #[test]
     fn test_regression_day5_part1() {
-        let mut vm = load_from_file("../day-05/part-1/input.txt").unwrap();
+        let path = input_path(5);
+        let mut vm = load_from_file(&path).unwrap();
         vm.io.input = Some(Box::new(vec![1].into_iter()));
         let mut count = 0;
         let mut last_value = 0;
@@ -304,293 +355,160 @@ mod test {
     // Day 5 part 2
     #[test]
     fn test_position_mode_1_lessthan() {
-        let mut vm = load_from_str("3,9,8,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![1].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], &[1]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_position_mode_1_equal() {
-        let mut vm = load_from_str("3,9,8,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![8].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], &[8]).0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_position_mode_1_greater_than() {
-        let mut vm = load_from_str("3,9,8,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], &[10]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_position_mode_2_lessthan() {
-        let mut vm = load_from_str("3,9,7,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![1].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], &[1]).0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_position_mode_2_equal() {
-        let mut vm = load_from_str("3,9,7,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![8].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], &[8]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_position_mode_2_greater_than() {
-        let mut vm = load_from_str("3,9,7,9,10,9,4,9,99,-1,8").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], &[10]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_immediate_mode_1_lessthan() {
-        let mut vm = load_from_str("3,3,1108,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![1].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], &[1]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_immediate_mode_1_equal() {
-        let mut vm = load_from_str("3,3,1108,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![8].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], &[8]).0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_immediate_mode_1_greater_than() {
-        let mut vm = load_from_str("3,3,1108,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 3, 1108, -1, 8, 3, 4, 3, 99], &[10]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_immediate_mode_2_lessthan() {
-        let mut vm = load_from_str("3,3,1107,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![1].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], &[1]).0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_immediate_mode_2_equal() {
-        let mut vm = load_from_str("3,3,1107,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![8].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], &[8]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_immediate_mode_2_greater_than() {
-        let mut vm = load_from_str("3,3,1107,-1,8,3,4,3,99").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 3, 1107, -1, 8, 3, 4, 3, 99], &[10]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_position_mode_jump_zero() {
-        let mut vm = load_from_str("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9").unwrap();
-        vm.io.input = Some(Box::new(vec![0].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(
+                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+                &[0]
+            )
+            .0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_position_mode_jump_non_zero() {
-        let mut vm = load_from_str("3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(
+                &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+                &[10]
+            )
+            .0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_immediate_mode_jump_zero() {
-        let mut vm = load_from_str("3,3,1105,-1,9,1101,0,0,12,4,12,99,1").unwrap();
-        vm.io.input = Some(Box::new(vec![0].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 0);
+        assert_eq!(
+            run_program(&[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1], &[0]).0,
+            vec![0]
+        );
     }
 
     #[test]
     fn test_immediate_mode_jump_non_zero() {
-        let mut vm = load_from_str("3,3,1105,-1,9,1101,0,0,12,4,12,99,1").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1);
+        assert_eq!(
+            run_program(&[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1], &[10]).0,
+            vec![1]
+        );
     }
 
     #[test]
     fn test_8_compare_less_than() {
-        let mut vm = load_from_str("3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99").unwrap();
-        vm.io.input = Some(Box::new(vec![5].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 999);
+        assert_eq!(run_program(&COMPARE_TO_8, &[5]).0, vec![999]);
     }
 
     #[test]
     fn test_8_compare_equal() {
-        let mut vm = load_from_str("3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99").unwrap();
-        vm.io.input = Some(Box::new(vec![8].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1000);
+        assert_eq!(run_program(&COMPARE_TO_8, &[8]).0, vec![1000]);
     }
 
     #[test]
     fn test_8_compare_greater_than() {
-        let mut vm = load_from_str("3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99").unwrap();
-        vm.io.input = Some(Box::new(vec![10].into_iter()));
-        let mut count = 0;
-        let mut last_value = 0;
-        vm.io.output = Some(Box::new(|value| {
-            count += 1;
-            last_value = value;
-        }));
-        vm.last().unwrap();
-        assert_eq!(count, 1);
-        assert_eq!(last_value, 1001);
+        assert_eq!(run_program(&COMPARE_TO_8, &[10]).0, vec![1001]);
     }
 
     // regression
     #[test]
     fn test_regression_day5_part2() {
-        let mut vm = load_from_file("../day-05/part-1/input.txt").unwrap();
+        let path = input_path(5);
+        let mut vm = load_from_file(&path).unwrap();
         vm.io.input = Some(Box::new(vec![5].into_iter()));
         let mut count = 0;
         let mut last_value = 0;
@@ -617,12 +535,9 @@ mod test {
     // should output a 16-digit number.
     #[test]
     fn test_day9_part1_16digit() {
-        let vm =
-            load_from_str("109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99").unwrap();
-        assert_eq!(
-            vm.collect::<Vec<i64>>(),
-            vec!(109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99)
-        );
+        let (outputs, _) = run_program(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[]);
+        assert_eq!(outputs, vec![1_219_070_632_396_864]);
+        assert_eq!(outputs[0].to_string().len(), 16);
     }
 
     // should output the large number in the middle.
@@ -631,4 +546,27 @@ mod test {
         let vm = load_from_str("104,1125899906842624,99").unwrap();
         assert_eq!(vm.collect::<Vec<i64>>(), vec!(1_125_899_906_842_624));
     }
+
+    #[test]
+    fn test_host_call() {
+        let mut vm = load_from_str("10,7,8,9,4,9,99,5,20,0").unwrap();
+        vm.io.register_host_call(5, "double", Box::new(|x| x * 2));
+        assert_eq!(vm.collect::<Vec<i64>>(), vec!(40));
+    }
+
+    #[test]
+    fn test_host_call_immediate_and_relative() {
+        let mut vm = load_from_str("109,10,21110,1,6,0,204,0,99").unwrap();
+        vm.io
+            .register_host_call(1, "increment", Box::new(|x| x + 1));
+        assert_eq!(vm.io.host_call_id("increment"), Some(1));
+        assert_eq!(vm.collect::<Vec<i64>>(), vec!(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown host call 3")]
+    fn test_unknown_host_call() {
+        let mut vm = load_from_str("1110,3,0,0,99").unwrap();
+        vm.next();
+    }
 }
@@ -1,30 +1,81 @@
 #[macro_use]
 extern crate log;
 
-use core::ops::{Index, IndexMut};
+use core::ops::{Index, IndexMut, Range};
 use std::cmp::{Eq, PartialEq};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+pub mod conformance;
+pub mod device;
+pub mod fuzz;
+pub mod history;
+pub mod host;
 pub mod io;
 pub mod lang;
+pub mod loader;
+pub mod program;
+
+pub trait Device<T> {
+    fn read(&mut self, offset: usize) -> T;
+    fn write(&mut self, offset: usize, value: T);
+}
 
 pub struct IntcodeVMMemory<T> {
     pub instruction_pointer: usize,
     pub memory: Vec<T>,
     pub metadata: Vec<T>,
+    pub devices: Vec<(Range<usize>, Box<dyn Device<T>>)>,
+    pub journal: Option<Vec<(usize, T, usize)>>,
 }
 
 impl<T> IntcodeVMMemory<T>
 where
     T: Copy,
 {
-    fn get(&self, position: usize, default: T) -> T {
+    pub fn attach_device(&mut self, addresses: Range<usize>, device: Box<dyn Device<T>>) {
+        if let Some((existing, _)) = self
+            .devices
+            .iter()
+            .find(|(existing, _)| existing.start < addresses.end && addresses.start < existing.end)
+        {
+            panic!(
+                "Device at {:?} overlaps existing device at {:?}",
+                addresses, existing
+            );
+        }
+        self.devices.push((addresses, device));
+    }
+
+    fn device_at(&mut self, position: usize) -> Option<(usize, &mut Box<dyn Device<T>>)> {
+        self.devices
+            .iter_mut()
+            .find(|(addresses, _)| addresses.contains(&position))
+            .map(|(addresses, device)| (position - addresses.start, device))
+    }
+
+    fn get(&mut self, position: usize, default: T) -> T {
+        if let Some((offset, device)) = self.device_at(position) {
+            return device.read(offset);
+        }
         *self.memory.get(position).unwrap_or(&default)
     }
 
     fn set(&mut self, position: usize, value: T, default_memory_value: T) {
+        if let Some((offset, device)) = self.device_at(position) {
+            return device.write(offset, value);
+        }
+        if let Some(journal) = self.journal.as_mut() {
+            journal.push((
+                position,
+                self.memory
+                    .get(position)
+                    .copied()
+                    .unwrap_or(default_memory_value),
+                self.memory.len(),
+            ));
+        }
         if position >= self.memory.len() {
             self.memory.resize(position + 1, default_memory_value)
         }
@@ -46,9 +97,37 @@ impl<T> IndexMut<usize> for IntcodeVMMemory<T> {
     }
 }
 
+pub struct HostCall<'a, T> {
+    pub name: String,
+    pub function: Box<dyn FnMut(T) -> T + 'a>,
+}
+
 pub struct IntcodeVMIO<'a, T> {
     pub input: Option<Box<dyn Iterator<Item = T> + 'a>>,
-    pub output: Option<Box<dyn FnMut(T) -> () + 'a>>,
+    pub output: Option<Box<dyn FnMut(T) + 'a>>,
+    pub host_calls: HashMap<T, HostCall<'a, T>>,
+}
+
+impl<'a, T> IntcodeVMIO<'a, T>
+where
+    T: Copy + Hash + Eq + PartialEq,
+{
+    pub fn register_host_call(&mut self, id: T, name: &str, function: Box<dyn FnMut(T) -> T + 'a>) {
+        self.host_calls.insert(
+            id,
+            HostCall {
+                name: name.to_string(),
+                function,
+            },
+        );
+    }
+
+    pub fn host_call_id(&self, name: &str) -> Option<T> {
+        self.host_calls
+            .iter()
+            .find(|(_, host_call)| host_call.name == name)
+            .map(|(&id, _)| id)
+    }
 }
 
 pub trait OpCode<T> {
@@ -64,6 +143,7 @@ pub struct IntcodeVM<'a, T> {
     pub op_codes: HashMap<T, Box<dyn OpCode<T>>>,
     pub op_code_map: &'a dyn Fn(T) -> T,
     pub io: IntcodeVMIO<'a, T>,
+    pub history: Option<history::History<T>>,
 }
 
 impl<'a, T> IntcodeVM<'a, T>
@@ -75,17 +155,54 @@ where
         op_codes: HashMap<T, Box<dyn OpCode<T>>>,
         op_code_map: &'a dyn Fn(T) -> T,
         input: Option<Box<dyn Iterator<Item = T>>>,
-        output: Option<Box<dyn FnMut(T) -> ()>>,
+        output: Option<Box<dyn FnMut(T)>>,
     ) -> IntcodeVM<'a, T> {
         IntcodeVM {
             memory: IntcodeVMMemory {
                 instruction_pointer: 0,
                 memory,
                 metadata: Vec::new(),
+                devices: Vec::new(),
+                journal: None,
             },
             op_codes,
             op_code_map,
-            io: IntcodeVMIO { input, output },
+            io: IntcodeVMIO {
+                input,
+                output,
+                host_calls: HashMap::new(),
+            },
+            history: None,
+        }
+    }
+}
+
+impl<T> IntcodeVM<'_, T>
+where
+    T: Copy + Hash + Eq + PartialEq + Debug,
+{
+    pub fn step(&mut self) -> Option<Option<T>> {
+        let op_code = (self.op_code_map)(self.memory.memory[self.memory.instruction_pointer]);
+        if !self.op_codes.contains_key(&op_code) {
+            panic!("Unknown OpCode! {:?}", op_code);
+        }
+
+        let undo_entry = self.begin_undo_entry();
+        let op = self.op_codes.get(&op_code);
+        match op.unwrap().execute(&mut self.memory, &mut self.io) {
+            Some((new_instruction_pointer, ret_val_option)) => {
+                debug!(
+                    "Processed op code: {:?}",
+                    self.memory.memory[self.memory.instruction_pointer]
+                );
+                self.memory.instruction_pointer = new_instruction_pointer;
+                self.finish_undo_entry(undo_entry);
+                Some(ret_val_option)
+            }
+            None => {
+                self.memory.journal = None;
+                None
+            }
         }
     }
 }
@@ -98,24 +215,8 @@ where
 
     fn next(&mut self) -> Option<T> {
         loop {
-            let op_code = (self.op_code_map)(self.memory.memory[self.memory.instruction_pointer]);
-            let op = self.op_codes.get(&op_code);
-            if op.is_none() {
-                panic!("Unknown OpCode! {:?}", op_code);
-            }
-
-            match op.unwrap().execute(&mut self.memory, &mut self.io) {
-                Some((new_instruction_pointer, ret_val_option)) => {
-                    debug!(
-                        "Processed op code: {:?}",
-                        self.memory.memory[self.memory.instruction_pointer]
-                    );
-                    self.memory.instruction_pointer = new_instruction_pointer;
-                    if let Some(ret_val) = ret_val_option {
-                        return Some(ret_val);
-                    }
-                }
-                None => return None,
+            if let Some(ret_val) = self.step()? {
+                return Some(ret_val);
             }
         }
     }
@@ -0,0 +1,315 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+// Encoded programs start with one of these headers, the leading zero byte
+// keeps them from ever being mistaken for text.
+pub const BINARY_MAGIC: &[u8; 4] = b"\0icb";
+pub const VARINT_MAGIC: &[u8; 4] = b"\0icv";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Binary,
+    Varint,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LoadError {
+    InvalidToken {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+    MissingValue {
+        line: usize,
+        column: usize,
+    },
+    Empty,
+    TruncatedBinary {
+        length: usize,
+    },
+    TruncatedVarint {
+        offset: usize,
+    },
+    VarintOverflow {
+        offset: usize,
+    },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            LoadError::InvalidToken {
+                line,
+                column,
+                token,
+            } => write!(
+                f,
+                "Invalid value '{}' at line {}, column {}",
+                token, line, column
+            ),
+            LoadError::MissingValue { line, column } => {
+                write!(f, "Missing value at line {}, column {}", line, column)
+            }
+            LoadError::Empty => write!(f, "Program contains no values"),
+            LoadError::TruncatedBinary { length } => write!(
+                f,
+                "Binary program length {} is not a multiple of 8 bytes",
+                length
+            ),
+            LoadError::TruncatedVarint { offset } => {
+                write!(f, "Varint at byte {} is truncated", offset)
+            }
+            LoadError::VarintOverflow { offset } => {
+                write!(f, "Varint at byte {} does not fit in 64 bits", offset)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+// Without a header, anything made only of printable ASCII and whitespace is
+// taken to be text and anything else headerless binary words.
+pub fn detect_format(bytes: &[u8]) -> Format {
+    if bytes.starts_with(BINARY_MAGIC) {
+        Format::Binary
+    } else if bytes.starts_with(VARINT_MAGIC) {
+        Format::Varint
+    } else if bytes
+        .iter()
+        .all(|byte| byte.is_ascii_graphic() || byte.is_ascii_whitespace())
+    {
+        Format::Text
+    } else {
+        Format::Binary
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    match detect_format(bytes) {
+        Format::Text => parse_text(&String::from_utf8_lossy(bytes)),
+        Format::Binary => parse_binary(bytes),
+        Format::Varint => parse_varint(bytes),
+    }
+}
+
+fn parse_token(token: &str, line: usize, column: usize) -> Result<i64, LoadError> {
+    token.parse().map_err(|_| LoadError::InvalidToken {
+        line,
+        column,
+        token: token.to_string(),
+    })
+}
+
+// Values are separated by commas, whitespace or newlines, and anything after a
+// '#' is a comment. A comma with no value before it is an error, but a
+// trailing comma is allowed so long programs can be split across lines.
+pub fn parse_text(text: &str) -> Result<Vec<i64>, LoadError> {
+    let mut values = vec![];
+    let mut value_since_comma = false;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let code = line.split('#').next().unwrap_or("");
+        let mut token_start = None;
+        for (column, character) in code.char_indices().chain(Some((code.len(), ' '))) {
+            let is_separator = character == ',' || character.is_whitespace();
+            match (token_start, is_separator) {
+                (None, false) => token_start = Some(column),
+                (Some(start), true) => {
+                    values.push(parse_token(&code[start..column], line_number, start + 1)?);
+                    value_since_comma = true;
+                    token_start = None;
+                }
+                _ => {}
+            }
+            if character == ',' && column < code.len() {
+                if !value_since_comma {
+                    return Err(LoadError::MissingValue {
+                        line: line_number,
+                        column: column + 1,
+                    });
+                }
+                value_since_comma = false;
+            }
+        }
+    }
+    if values.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    Ok(values)
+}
+
+// Little endian words, after the magic header if there is one.
+pub fn parse_binary(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    let bytes = bytes.strip_prefix(&BINARY_MAGIC[..]).unwrap_or(bytes);
+    if bytes.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    if !bytes.len().is_multiple_of(8) {
+        return Err(LoadError::TruncatedBinary {
+            length: bytes.len(),
+        });
+    }
+    Ok(bytes
+        .chunks(8)
+        .map(|word| {
+            let mut buffer = [0; 8];
+            buffer.copy_from_slice(word);
+            i64::from_le_bytes(buffer)
+        })
+        .collect())
+}
+
+// Zigzag encoded LEB128 words after the magic header, so small negative
+// numbers stay small.
+pub fn parse_varint(bytes: &[u8]) -> Result<Vec<i64>, LoadError> {
+    let mut values = vec![];
+    let mut offset = VARINT_MAGIC.len();
+    while offset < bytes.len() {
+        let start = offset;
+        let mut encoded: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = *bytes
+                .get(offset)
+                .ok_or(LoadError::TruncatedVarint { offset: start })?;
+            offset += 1;
+            if shift >= 64 || (shift == 63 && byte & 0x7e != 0) {
+                return Err(LoadError::VarintOverflow { offset: start });
+            }
+            encoded |= u64::from(byte & 0x7f) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        values.push((encoded >> 1) as i64 ^ -((encoded & 1) as i64));
+    }
+    if values.is_empty() {
+        return Err(LoadError::Empty);
+    }
+    Ok(values)
+}
+
+pub fn encode(program: &[i64], format: Format) -> Vec<u8> {
+    match format {
+        Format::Text => program
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+            .into_bytes(),
+        Format::Binary => {
+            let mut bytes = BINARY_MAGIC.to_vec();
+            for &value in program {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes
+        }
+        Format::Varint => {
+            let mut bytes = VARINT_MAGIC.to_vec();
+            for &value in program {
+                let mut encoded = ((value << 1) ^ (value >> 63)) as u64;
+                while encoded >= 0x80 {
+                    bytes.push((encoded as u8 & 0x7f) | 0x80);
+                    encoded >>= 7;
+                }
+                bytes.push(encoded as u8);
+            }
+            bytes
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PROGRAM: [i64; 6] = [109, -1, 204, 1_125_899_906_842_624, i64::MIN, i64::MAX];
+
+    #[test]
+    fn test_text_with_comments_and_newlines() {
+        let text = "# Adds two numbers\n1, 9, 10, 3,  # add\n\t2,3,11,0,\n99\n30 40 50\n";
+        assert_eq!(
+            parse_text(text),
+            Ok(vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50])
+        );
+    }
+
+    #[test]
+    fn test_text_errors() {
+        assert_eq!(
+            parse_text("1,2,3\n4,x5,6"),
+            Err(LoadError::InvalidToken {
+                line: 2,
+                column: 3,
+                token: "x5".to_string()
+            })
+        );
+        assert_eq!(
+            parse_text("1,2,,3"),
+            Err(LoadError::MissingValue { line: 1, column: 5 })
+        );
+        assert_eq!(
+            parse_text(",1,2"),
+            Err(LoadError::MissingValue { line: 1, column: 1 })
+        );
+        assert_eq!(
+            parse_text("1,\n  ,2"),
+            Err(LoadError::MissingValue { line: 2, column: 3 })
+        );
+        assert_eq!(parse_text(" # nothing here\n"), Err(LoadError::Empty));
+        assert_eq!(
+            parse_text("99999999999999999999").unwrap_err().to_string(),
+            "Invalid value '99999999999999999999' at line 1, column 1"
+        );
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for &format in &[Format::Text, Format::Binary, Format::Varint] {
+            let bytes = encode(&PROGRAM, format);
+            assert_eq!(detect_format(&bytes), format);
+            assert_eq!(parse(&bytes), Ok(PROGRAM.to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_printable_binary() {
+        // Every byte of these words is printable, so only the header tells
+        // them apart from text.
+        let program = [0x2020_2020_2020_2031, 0x0a0a_0a0a_3232_3232];
+        let bytes = encode(&program, Format::Binary);
+        assert_eq!(detect_format(&bytes), Format::Binary);
+        assert_eq!(parse(&bytes), Ok(program.to_vec()));
+        assert_eq!(detect_format(&bytes[4..]), Format::Text);
+        assert_eq!(parse_binary(&bytes[4..]), Ok(program.to_vec()));
+    }
+
+    #[test]
+    fn test_varint_is_compact() {
+        assert_eq!(encode(&[1, -1, 64], Format::Varint)[4..], [2, 1, 128, 1]);
+    }
+
+    #[test]
+    fn test_binary_and_varint_errors() {
+        assert_eq!(
+            parse_binary(&[99, 0, 0, 0, 0, 0, 0, 0, 1]),
+            Err(LoadError::TruncatedBinary { length: 9 })
+        );
+        assert_eq!(parse_binary(BINARY_MAGIC), Err(LoadError::Empty));
+        assert_eq!(
+            parse_varint(b"\0icv\x02\x80"),
+            Err(LoadError::TruncatedVarint { offset: 5 })
+        );
+        let mut overflow = VARINT_MAGIC.to_vec();
+        overflow.extend(vec![0xff; 10]);
+        overflow.push(0);
+        assert_eq!(
+            parse_varint(&overflow),
+            Err(LoadError::VarintOverflow { offset: 4 })
+        );
+        assert_eq!(parse_varint(VARINT_MAGIC), Err(LoadError::Empty));
+    }
+}
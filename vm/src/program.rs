@@ -0,0 +1,190 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use super::lang::{get_ops, load_memory_from_file, op_code_lookup};
+use super::loader::{self, LoadError};
+use super::IntcodeVM;
+
+// A parsed program image. Cloning only bumps a reference count, so one parse
+// can be shared between any number of variants and threads.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    image: Arc<Vec<i64>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Patch {
+    pub name: String,
+    pub address: usize,
+    pub value: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgramBuilder {
+    program: Program,
+    patches: Vec<Patch>,
+}
+
+impl Program {
+    pub fn new(image: Vec<i64>) -> Program {
+        Program {
+            image: Arc::new(image),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Program, LoadError> {
+        Ok(Program::new(loader::parse_text(text)?))
+    }
+
+    pub fn from_file(filename: &str) -> Result<Program, Box<dyn Error>> {
+        Ok(Program::new(load_memory_from_file(filename)?))
+    }
+
+    pub fn image(&self) -> &[i64] {
+        &self.image
+    }
+
+    pub fn variant(&self) -> ProgramBuilder {
+        ProgramBuilder {
+            program: self.clone(),
+            patches: vec![],
+        }
+    }
+
+    pub fn instantiate<'a>(&self) -> IntcodeVM<'a, i64> {
+        self.variant().instantiate()
+    }
+
+    pub fn reset(&self, vm: &mut IntcodeVM<i64>) {
+        self.variant().reset(vm)
+    }
+}
+
+impl ProgramBuilder {
+    // Patches are applied in order, one with the same name as an earlier patch
+    // replaces it.
+    pub fn patch(mut self, name: &str, address: usize, value: i64) -> ProgramBuilder {
+        self.patches.retain(|patch| patch.name != name);
+        self.patches.push(Patch {
+            name: name.to_string(),
+            address,
+            value,
+        });
+        self
+    }
+
+    pub fn noun(self, value: i64) -> ProgramBuilder {
+        self.patch("noun", 1, value)
+    }
+
+    pub fn verb(self, value: i64) -> ProgramBuilder {
+        self.patch("verb", 2, value)
+    }
+
+    // Day 13: setting the first address to 2 plays without inserting quarters.
+    pub fn free_play(self) -> ProgramBuilder {
+        self.patch("free play", 0, 2)
+    }
+
+    pub fn patches(&self) -> &[Patch] {
+        &self.patches
+    }
+
+    fn apply(&self, memory: &mut Vec<i64>) {
+        for patch in &self.patches {
+            if patch.address >= memory.len() {
+                memory.resize(patch.address + 1, 0);
+            }
+            memory[patch.address] = patch.value;
+        }
+    }
+
+    pub fn build(&self) -> Program {
+        if self.patches.is_empty() {
+            return self.program.clone();
+        }
+        let mut image = self.program.image.to_vec();
+        self.apply(&mut image);
+        Program::new(image)
+    }
+
+    pub fn instantiate<'a>(&self) -> IntcodeVM<'a, i64> {
+        let mut memory = self.program.image.to_vec();
+        self.apply(&mut memory);
+        IntcodeVM::create(memory, get_ops(), &op_code_lookup, None, None)
+    }
+
+    // Puts an existing VM back to the start of this variant, reusing its
+    // memory allocation. IO, host calls and devices are kept, but any history
+    // is dropped as it no longer describes the program being run.
+    pub fn reset(&self, vm: &mut IntcodeVM<i64>) {
+        vm.memory.memory.clear();
+        vm.memory.memory.extend_from_slice(&self.program.image);
+        self.apply(&mut vm.memory.memory);
+        vm.memory.instruction_pointer = 0;
+        vm.memory.metadata.clear();
+        vm.memory.journal = None;
+        vm.history = None;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solution::input_path;
+
+    #[test]
+    fn test_variants_share_the_image() {
+        let program = Program::parse("1,0,0,0,99").unwrap();
+        let variant = program.variant().noun(4).verb(4).build();
+        assert_eq!(variant.image(), &[1, 4, 4, 0, 99]);
+        assert_eq!(program.image(), &[1, 0, 0, 0, 99]);
+        assert!(Arc::ptr_eq(
+            &program.image,
+            &program.variant().build().image
+        ));
+    }
+
+    #[test]
+    fn test_named_patches_replace_earlier_ones() {
+        let builder = Program::parse("1,0,0,0,99")
+            .unwrap()
+            .variant()
+            .noun(1)
+            .patch("scratch", 7, 3)
+            .noun(2);
+        assert_eq!(
+            builder.patches(),
+            &[
+                Patch {
+                    name: "scratch".to_string(),
+                    address: 7,
+                    value: 3
+                },
+                Patch {
+                    name: "noun".to_string(),
+                    address: 1,
+                    value: 2
+                }
+            ]
+        );
+        assert_eq!(builder.build().image(), &[1, 2, 0, 0, 99, 0, 0, 3]);
+    }
+
+    #[test]
+    fn test_reset_reuses_the_vm() {
+        let program = Program::parse("109,5,21101,1,2,0,204,0,99").unwrap();
+        let mut vm = program.instantiate();
+        assert_eq!(vm.by_ref().collect::<Vec<i64>>(), vec![3]);
+        program.variant().patch("left", 3, 40).reset(&mut vm);
+        assert_eq!(vm.by_ref().collect::<Vec<i64>>(), vec![42]);
+        assert_eq!(vm.memory.memory, vec![109, 5, 21101, 40, 2, 42, 204, 0, 99]);
+    }
+
+    #[test]
+    fn test_free_play() {
+        let program = Program::from_file(&input_path(13)).unwrap();
+        assert_eq!(program.image()[0], 1);
+        assert_eq!(program.variant().free_play().build().image()[0], 2);
+    }
+}